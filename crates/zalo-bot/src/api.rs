@@ -0,0 +1,117 @@
+use serde::Deserialize;
+
+use crate::error::{BotError, BotResult};
+
+/// Response envelope shared by the Zalo OA API endpoints.
+///
+/// Every endpoint answers with `{ "error": <code>, "message": <text> }` and,
+/// on success (`error == 0`), a `data` payload. Client methods deserialise the
+/// body into this wrapper and call [`into_result`](Self::into_result) instead
+/// of inspecting the fields themselves.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use zalo_bot::api::ZaloApiEnvelope;
+///
+/// #[derive(Deserialize)]
+/// struct Profile {
+///     display_name: String,
+/// }
+///
+/// let body = r#"{"error":0,"message":"Success","data":{"display_name":"Lan"}}"#;
+/// let envelope: ZaloApiEnvelope<Profile> = serde_json::from_str(body)?;
+/// assert_eq!(envelope.into_result()?.display_name, "Lan");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct ZaloApiEnvelope<T> {
+    error: i64,
+    #[serde(default)]
+    message: String,
+    data: Option<T>,
+}
+
+impl<T> ZaloApiEnvelope<T> {
+    /// Returns the error code; `0` means success.
+    #[must_use]
+    pub fn error_code(&self) -> i64 {
+        self.error
+    }
+
+    /// Returns the message reported by the API.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns `true` when the API reported success.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.error == 0
+    }
+
+    /// Converts the envelope into its payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BotError::Api`] carrying the reported code and message when
+    /// the code is non-zero, or when a successful response lacks `data`.
+    pub fn into_result(self) -> BotResult<T> {
+        match (self.error, self.data) {
+            (0, Some(data)) => Ok(data),
+            (0, None) => Err(BotError::Api {
+                code: 0,
+                message: "successful response is missing `data`".to_owned(),
+            }),
+            (code, _) => Err(BotError::Api {
+                code,
+                message: self.message,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+    use zalo_types::{AppError, AppErrorKind};
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Follower {
+        user_id: String,
+    }
+
+    #[test]
+    fn success_envelope_yields_typed_payload() {
+        let body = r#"{"error":0,"message":"Success","data":{"user_id":"42"}}"#;
+        let envelope: ZaloApiEnvelope<Follower> = serde_json::from_str(body).expect("envelope");
+
+        assert!(envelope.is_success());
+        assert_eq!(
+            envelope.into_result().expect("payload"),
+            Follower {
+                user_id: "42".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn error_envelope_maps_to_api_error() {
+        let body = r#"{"error":-216,"message":"Access token is invalid"}"#;
+        let envelope: ZaloApiEnvelope<Follower> = serde_json::from_str(body).expect("envelope");
+
+        let error = envelope.into_result().expect_err("api error");
+        assert!(matches!(
+            error,
+            BotError::Api { code: -216, ref message } if message == "Access token is invalid"
+        ));
+        assert!(matches!(
+            AppError::from(error).kind,
+            AppErrorKind::ExternalApi
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use tracing::dispatcher::{self, Dispatch};
+use zalo_types::{AppConfig, ConfigLoader};
+
+use crate::error::{BotError, BotResult, ObservabilityError};
+use crate::observability::build_tracing_dispatch;
+use crate::webhook::WebhookVerifier;
+
+/// Everything a bot needs at startup, built from a single configuration load.
+///
+/// The bootstrap loads the configuration, prepares the tracing dispatcher and
+/// constructs the webhook verifier when a secret is configured. Installing the
+/// dispatcher is left to [`install_tracing`](Self::install_tracing) so tests
+/// and embedders can keep their own subscriber.
+#[derive(Clone, Debug)]
+pub struct AppBootstrap {
+    config: AppConfig,
+    dispatch: Dispatch,
+    verifier: Option<WebhookVerifier>,
+}
+
+impl AppBootstrap {
+    /// Bootstraps from the default `ZALO_BOT_` environment configuration.
+    ///
+    /// # Errors
+    ///
+    /// See [`from_loader`](Self::from_loader).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::AppBootstrap;
+    ///
+    /// let bootstrap = AppBootstrap::from_env()?;
+    /// tracing::dispatcher::with_default(bootstrap.dispatch(), || {
+    ///     tracing::info!(environment = bootstrap.config().environment().as_str(), "ready");
+    /// });
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_env() -> BotResult<Self> {
+        Self::from_loader(&ConfigLoader::default())
+    }
+
+    /// Bootstraps from a custom configuration loader.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BotError::Types`] when the configuration cannot be loaded,
+    /// [`BotError::Observability`] when the logging filter is invalid and
+    /// [`BotError::Signature`] when a configured webhook secret is rejected.
+    pub fn from_loader(loader: &ConfigLoader) -> BotResult<Self> {
+        let config = loader.load()?;
+        let dispatch = build_tracing_dispatch(&config)?;
+        let verifier = if config.webhook().has_secret() {
+            Some(WebhookVerifier::from_config(config.webhook())?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            config,
+            dispatch,
+            verifier,
+        })
+    }
+
+    /// Returns the loaded configuration.
+    #[must_use]
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// Returns the tracing dispatcher built from the logging configuration.
+    #[must_use]
+    pub fn dispatch(&self) -> &Dispatch {
+        &self.dispatch
+    }
+
+    /// Returns the webhook verifier, or `None` when no secret is configured.
+    #[must_use]
+    pub fn verifier(&self) -> Option<&WebhookVerifier> {
+        self.verifier.as_ref()
+    }
+
+    /// Installs the prepared dispatcher as the global default subscriber.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BotError::Observability`] when a global subscriber has
+    /// already been installed.
+    pub fn install_tracing(&self) -> BotResult<()> {
+        dispatcher::set_global_default(self.dispatch.clone())
+            .map_err(ObservabilityError::from)
+            .map_err(BotError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zalo_types::{Environment, WebhookConfig};
+
+    #[test]
+    fn bootstraps_config_and_verifier() {
+        let defaults = AppConfig::default()
+            .with_environment(Environment::Staging)
+            .with_webhook(WebhookConfig::default().with_secret("secret"));
+        let loader = ConfigLoader::new("ZALO_BOOTSTRAP_TEST_").with_defaults(defaults);
+
+        let bootstrap = AppBootstrap::from_loader(&loader).expect("bootstrap");
+        let verifier = bootstrap.verifier().expect("verifier configured");
+        let signature = verifier.sign_payload(b"payload").expect("signature");
+
+        assert_eq!(bootstrap.config().environment(), Environment::Staging);
+        verifier
+            .verify(b"payload", Some(&signature))
+            .expect("signature should validate");
+    }
+
+    #[test]
+    fn verifier_is_absent_without_secret() {
+        let loader = ConfigLoader::new("ZALO_BOOTSTRAP_TEST_");
+
+        let bootstrap = AppBootstrap::from_loader(&loader).expect("bootstrap");
+
+        assert!(bootstrap.verifier().is_none());
+    }
+}
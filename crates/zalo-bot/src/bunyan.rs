@@ -0,0 +1,180 @@
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::{FormatEvent, FormatFields, Writer};
+use tracing_subscriber::fmt::FmtContext;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::observability::FieldRedaction;
+
+/// Bunyan record schema version.
+const BUNYAN_VERSION: u8 = 0;
+
+/// Event formatter emitting one Bunyan JSON record per line.
+///
+/// Records carry the core Bunyan fields (`v`, `level` as a number, `name`,
+/// `hostname`, `pid`, `time`, `msg`) followed by the event fields and its
+/// `target`. Event fields pass through `redaction` first.
+#[derive(Clone, Debug)]
+pub(crate) struct BunyanFormat {
+    name: String,
+    hostname: String,
+    pid: u32,
+    redaction: FieldRedaction,
+}
+
+impl BunyanFormat {
+    /// Captures the process identity reported in every record.
+    pub(crate) fn new(redaction: FieldRedaction) -> Self {
+        let name = std::env::args()
+            .next()
+            .as_deref()
+            .and_then(|path| std::path::Path::new(path).file_stem()?.to_str())
+            .map_or_else(|| "zalo".to_owned(), str::to_owned);
+        let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_owned());
+
+        Self {
+            name,
+            hostname,
+            pid: std::process::id(),
+            redaction,
+        }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for BunyanFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut record = Map::new();
+        event.record(&mut self.redaction.visitor(FieldVisitor(&mut record)));
+        record.entry("msg").or_insert_with(|| Value::from(""));
+
+        // Core fields are inserted last so event fields cannot shadow them.
+        record.insert("v".to_owned(), BUNYAN_VERSION.into());
+        record.insert("level".to_owned(), level_number(metadata.level()).into());
+        record.insert("name".to_owned(), self.name.clone().into());
+        record.insert("hostname".to_owned(), self.hostname.clone().into());
+        record.insert("pid".to_owned(), self.pid.into());
+        record.insert("time".to_owned(), format_time(SystemTime::now()).into());
+        record.insert("target".to_owned(), metadata.target().into());
+
+        let line = serde_json::to_string(&record).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+/// Maps tracing levels onto the numeric Bunyan levels.
+fn level_number(level: &Level) -> u8 {
+    match *level {
+        Level::TRACE => 10,
+        Level::DEBUG => 20,
+        Level::INFO => 30,
+        Level::WARN => 40,
+        Level::ERROR => 50,
+    }
+}
+
+/// Formats a timestamp as RFC 3339 UTC with millisecond precision.
+pub(crate) fn format_time(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+    let seconds = elapsed.as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let second_of_day = seconds % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        second_of_day / 3_600,
+        second_of_day % 3_600 / 60,
+        second_of_day % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+/// Converts days since the Unix epoch into a proleptic Gregorian date.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Collects event fields into the JSON record, renaming `message` to `msg`.
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl FieldVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        let key = match field.name() {
+            "message" => "msg",
+            name => name,
+        };
+        self.0.insert(key.to_owned(), value);
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_rfc3339_timestamps() {
+        assert_eq!(format_time(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_time(UNIX_EPOCH + Duration::from_millis(1_700_000_000_250)),
+            "2023-11-14T22:13:20.250Z"
+        );
+        assert_eq!(
+            format_time(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00.000Z"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn console_log(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(message: &str);
+}
+
+/// Writer handing each formatted record to the browser console.
+///
+/// `ERROR` records go to `console.error`, `WARN` records to `console.warn`
+/// and everything else to `console.log`.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ConsoleWriter;
+
+impl<'a> MakeWriter<'a> for ConsoleWriter {
+    type Writer = ConsoleRecord;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ConsoleRecord::new(console_log)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        let sink: fn(&str) = match *meta.level() {
+            Level::ERROR => console_error,
+            Level::WARN => console_warn,
+            _ => console_log,
+        };
+        ConsoleRecord::new(sink)
+    }
+}
+
+/// Buffers one record and passes it to the console when dropped.
+pub(crate) struct ConsoleRecord {
+    buffer: Vec<u8>,
+    sink: fn(&str),
+}
+
+impl ConsoleRecord {
+    fn new(sink: fn(&str)) -> Self {
+        Self {
+            buffer: Vec::new(),
+            sink,
+        }
+    }
+}
+
+impl io::Write for ConsoleRecord {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for ConsoleRecord {
+    fn drop(&mut self) {
+        if !self.buffer.is_empty() {
+            let record = String::from_utf8_lossy(&self.buffer);
+            (self.sink)(record.trim_end_matches('\n'));
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use hmac::digest::InvalidLength;
+use std::path::PathBuf;
+
 use thiserror::Error;
 use tracing::dispatcher::SetGlobalDefaultError;
+use tracing_appender::rolling::InitError;
 use tracing_subscriber::filter::ParseError as FilterParseError;
+use zalo_signing::InvalidLength;
 use zalo_types::{AppError, AppErrorKind, AppResult, TypesError};
 
 /// Convenient result alias for bot-specific operations.
@@ -19,6 +22,23 @@ pub enum BotError {
     /// Incoming webhook signature is not valid.
     #[error(transparent)]
     Signature(#[from] SignatureError),
+    /// Multipart webhook body is malformed.
+    #[error(transparent)]
+    Multipart(#[from] MultipartError),
+    /// Verified webhook payload does not match the expected event shape.
+    #[error("invalid webhook payload: {0}")]
+    Payload(#[source] serde_json::Error),
+    /// The Zalo OA API answered with a non-zero error code.
+    #[error("zalo api error {code}: {message}")]
+    Api {
+        /// Error code reported in the response envelope.
+        code: i64,
+        /// Error message reported in the response envelope.
+        message: String,
+    },
+    /// A verified webhook source exceeded its request budget.
+    #[error("webhook source `{0}` exceeded its rate limit")]
+    RateLimited(String),
 }
 
 impl From<BotError> for AppError {
@@ -27,6 +47,12 @@ impl From<BotError> for AppError {
             BotError::Types(inner) => inner.into(),
             BotError::Observability(inner) => inner.into(),
             BotError::Signature(inner) => inner.into(),
+            BotError::Multipart(inner) => inner.into(),
+            BotError::Payload(_) => AppError::with(AppErrorKind::Validation, error.to_string()),
+            BotError::Api { .. } => AppError::with(AppErrorKind::ExternalApi, error.to_string()),
+            BotError::RateLimited(_) => {
+                AppError::with(AppErrorKind::RateLimited, error.to_string())
+            }
         }
     }
 }
@@ -43,6 +69,24 @@ pub enum ObservabilityError {
         #[source]
         source: FilterParseError,
     },
+    /// File output was requested without a log directory.
+    #[error("file log output requires `logging.directory`")]
+    MissingDirectory,
+    /// Console output was requested without the `wasm` feature.
+    #[error("console log output requires the `wasm` feature")]
+    ConsoleUnavailable,
+    /// MessagePack logs were requested without the `msgpack` feature.
+    #[error("msgpack log format requires the `msgpack` feature")]
+    MsgPackUnavailable,
+    /// The rolling log file could not be created.
+    #[error("failed to open log files in {directory}: {source}")]
+    FileOutput {
+        /// Directory the log files were meant to be written to.
+        directory: PathBuf,
+        /// Source error raised by `tracing-appender`.
+        #[source]
+        source: InitError,
+    },
     /// Failed to install the global tracing subscriber.
     #[error("failed to install tracing subscriber: {source}")]
     Install {
@@ -55,7 +99,11 @@ pub enum ObservabilityError {
 impl From<ObservabilityError> for AppError {
     fn from(error: ObservabilityError) -> Self {
         match &error {
-            ObservabilityError::InvalidFilter { .. } => {
+            ObservabilityError::InvalidFilter { .. }
+            | ObservabilityError::MissingDirectory
+            | ObservabilityError::ConsoleUnavailable
+            | ObservabilityError::MsgPackUnavailable
+            | ObservabilityError::FileOutput { .. } => {
                 AppError::with(AppErrorKind::Config, error.to_string())
             }
             ObservabilityError::Install { .. } => {
@@ -77,21 +125,66 @@ pub enum SignatureError {
     /// The configured secret has an invalid length for the HMAC algorithm.
     #[error("invalid secret length: {0}")]
     InvalidSecretLength(#[from] InvalidLength),
+    /// No webhook secret has been configured.
+    #[error("no webhook secret configured")]
+    MissingSecret,
+    /// More neighbouring time buckets were requested than
+    /// [`MAX_SKEW_BUCKETS`](crate::webhook::MAX_SKEW_BUCKETS) allows.
+    #[error("time bucket skew of {0} exceeds the maximum of {max}", max = crate::webhook::MAX_SKEW_BUCKETS)]
+    SkewTooLarge(u64),
+    /// A field covered by the signature is absent from the payload.
+    #[error("signed field `{0}` is missing from the webhook payload")]
+    MissingField(String),
+    /// The signed timestamp is older than the accepted age.
+    #[error("webhook timestamp has expired")]
+    Expired,
+    /// The signed timestamp is further in the future than the accepted skew.
+    #[error("webhook timestamp is too far in the future")]
+    TimestampInFuture,
+    /// The signature header names an algorithm the verifier does not accept.
+    #[error("signature header names unaccepted algorithm `{0}`")]
+    AlgorithmMismatch(String),
 }
 
 impl From<SignatureError> for AppError {
     fn from(error: SignatureError) -> Self {
         match &error {
-            SignatureError::Missing | SignatureError::VerificationFailed => {
+            SignatureError::Missing
+            | SignatureError::VerificationFailed
+            | SignatureError::Expired
+            | SignatureError::TimestampInFuture
+            | SignatureError::AlgorithmMismatch(_) => {
                 AppError::with(AppErrorKind::Unauthorized, error.to_string())
             }
-            SignatureError::InvalidSecretLength(_) => {
+            SignatureError::InvalidSecretLength(_)
+            | SignatureError::MissingSecret
+            | SignatureError::SkewTooLarge(_) => {
                 AppError::with(AppErrorKind::Config, error.to_string())
             }
+            SignatureError::MissingField(_) => {
+                AppError::with(AppErrorKind::Validation, error.to_string())
+            }
         }
     }
 }
 
+/// Errors emitted while parsing `multipart/form-data` webhook bodies.
+#[derive(Clone, Copy, Debug, Error, Eq, PartialEq)]
+pub enum MultipartError {
+    /// The body does not contain the announced boundary.
+    #[error("multipart boundary not found in body")]
+    MissingBoundary,
+    /// A part is truncated or has malformed headers.
+    #[error("malformed multipart part")]
+    MalformedPart,
+}
+
+impl From<MultipartError> for AppError {
+    fn from(error: MultipartError) -> Self {
+        AppError::with(AppErrorKind::Validation, error.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
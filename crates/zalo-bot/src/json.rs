@@ -0,0 +1,170 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::Record;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::{FormatEvent, FormatFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormattedFields};
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+
+use crate::bunyan::format_time;
+use crate::observability::FieldRedaction;
+
+/// Event formatter emitting one JSON object per line.
+///
+/// Records follow the layout of `tracing_subscriber`'s JSON format
+/// (`timestamp`, `level`, `fields`, `target`, and `span`/`spans` when inside a
+/// span), but every event field passes through a [`FieldRedaction`] first.
+/// Span fields are stored by [`JsonFields`], which applies the same
+/// redaction.
+#[derive(Clone, Debug)]
+pub(crate) struct JsonFormat {
+    redaction: FieldRedaction,
+}
+
+impl JsonFormat {
+    /// Creates a formatter rewriting event fields with `redaction`.
+    pub(crate) fn new(redaction: FieldRedaction) -> Self {
+        Self { redaction }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = Map::new();
+        event.record(&mut self.redaction.visitor(FieldVisitor(&mut fields)));
+
+        let mut record = Map::new();
+        record.insert(
+            "timestamp".to_owned(),
+            format_time(SystemTime::now()).into(),
+        );
+        record.insert("level".to_owned(), metadata.level().as_str().into());
+        record.insert("fields".to_owned(), Value::Object(fields));
+        record.insert("target".to_owned(), metadata.target().into());
+
+        let current = event
+            .parent()
+            .and_then(|id| ctx.span(id))
+            .or_else(|| ctx.lookup_current());
+        if let Some(current) = current {
+            let spans: Vec<Value> = current
+                .scope()
+                .from_root()
+                .map(|span| span_object::<S, N>(&span))
+                .collect();
+            record.insert("span".to_owned(), span_object::<S, N>(&current));
+            record.insert("spans".to_owned(), spans.into());
+        }
+
+        let line = serde_json::to_string(&record).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+/// Renders a span as its stored JSON fields plus its `name`.
+fn span_object<S, N>(span: &SpanRef<'_, S>) -> Value
+where
+    S: for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    let extensions = span.extensions();
+    let mut object = extensions
+        .get::<FormattedFields<N>>()
+        .and_then(|fields| serde_json::from_str::<Map<String, Value>>(&fields.fields).ok())
+        .unwrap_or_default();
+    object.insert("name".to_owned(), span.name().into());
+    Value::Object(object)
+}
+
+/// Span field formatter storing fields as a JSON object for [`JsonFormat`].
+#[derive(Clone, Debug)]
+pub(crate) struct JsonFields {
+    redaction: FieldRedaction,
+}
+
+impl JsonFields {
+    /// Creates a formatter rewriting span fields with `redaction`.
+    pub(crate) fn new(redaction: FieldRedaction) -> Self {
+        Self { redaction }
+    }
+
+    fn write_fields<R: RecordFields>(
+        &self,
+        mut object: Map<String, Value>,
+        fields: R,
+    ) -> Map<String, Value> {
+        fields.record(&mut self.redaction.visitor(FieldVisitor(&mut object)));
+        object
+    }
+}
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        let object = self.write_fields(Map::new(), fields);
+        write!(writer, "{}", Value::Object(object))
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &Record<'_>,
+    ) -> fmt::Result {
+        let existing = serde_json::from_str(&current.fields).unwrap_or_default();
+        let object = self.write_fields(existing, fields);
+        current.fields = Value::Object(object).to_string();
+        Ok(())
+    }
+}
+
+/// Collects fields into a JSON object.
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl FieldVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        self.0.insert(field.name().to_owned(), value);
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
@@ -5,13 +5,32 @@
 //! The crate bundles reusable observability helpers and webhook signature
 //! verification suitable for building OA bots and webhooks.
 
+/// Zalo OA API response envelope handling.
+pub mod api;
+/// One-call startup sequence combining configuration, tracing and webhooks.
+pub mod bootstrap;
+mod bunyan;
+#[cfg(feature = "wasm")]
+mod console;
 /// Error definitions for the bot crate.
 pub mod error;
+mod json;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+/// Zero-copy `multipart/form-data` parsing for webhook callbacks.
+pub mod multipart;
 /// Observability helpers wrapping `tracing` initialisation.
 pub mod observability;
+/// Per-source throttling of verified webhook deliveries.
+pub mod rate_limit;
+mod verification_cache;
 /// Webhook signature verification helpers.
 pub mod webhook;
 
-pub use error::{BotError, BotResult, ObservabilityError, SignatureError};
-pub use observability::{build_tracing_dispatch, init_tracing};
-pub use webhook::WebhookVerifier;
+pub use api::ZaloApiEnvelope;
+pub use bootstrap::AppBootstrap;
+pub use error::{BotError, BotResult, MultipartError, ObservabilityError, SignatureError};
+pub use observability::{build_tracing_dispatch, init_tracing, init_tracing_or_warn};
+pub use rate_limit::{FixedWindowRateLimiter, RateLimiter};
+pub use webhook::{VerificationOutcome, WebhookEventEnvelope, WebhookVerifier};
+pub use zalo_signing::SignatureAlgorithm;
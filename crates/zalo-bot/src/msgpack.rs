@@ -0,0 +1,108 @@
+use std::io::Write;
+use std::time::SystemTime;
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::bunyan::format_time;
+use crate::observability::FieldRedaction;
+
+/// Layer writing each event as a length-prefixed MessagePack frame.
+///
+/// A frame is the length of the encoded record as a big-endian `u32`,
+/// followed by a MessagePack map holding the event fields (the message under
+/// `message`) and the `timestamp`, `level` and `target` of the event. The
+/// output is binary, so it is written by a layer of its own rather than
+/// through `tracing_subscriber::fmt`. Event fields pass through `redaction`
+/// first.
+#[derive(Clone, Debug)]
+pub(crate) struct MsgPackLayer<W> {
+    make_writer: W,
+    redaction: FieldRedaction,
+}
+
+impl<W> MsgPackLayer<W> {
+    /// Creates a layer writing frames to `make_writer`.
+    pub(crate) fn new(make_writer: W, redaction: FieldRedaction) -> Self {
+        Self {
+            make_writer,
+            redaction,
+        }
+    }
+}
+
+impl<S, W> Layer<S> for MsgPackLayer<W>
+where
+    S: Subscriber,
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut record = Map::new();
+        event.record(&mut self.redaction.visitor(FieldVisitor(&mut record)));
+
+        // Core fields are inserted last so event fields cannot shadow them.
+        record.insert(
+            "timestamp".to_owned(),
+            format_time(SystemTime::now()).into(),
+        );
+        record.insert("level".to_owned(), metadata.level().as_str().into());
+        record.insert("target".to_owned(), metadata.target().into());
+
+        let Some(frame) = encode_frame(&record) else {
+            return;
+        };
+        // Logging must not fail the caller; a lost record is the only option.
+        let _ = self.make_writer.make_writer_for(metadata).write_all(&frame);
+    }
+}
+
+/// Encodes a record as a length-prefixed frame, or `None` when it does not
+/// fit the `u32` length prefix.
+fn encode_frame(record: &Map<String, Value>) -> Option<Vec<u8>> {
+    let body = rmp_serde::to_vec(record).ok()?;
+    let len = u32::try_from(body.len()).ok()?;
+
+    let mut frame = Vec::with_capacity(body.len() + 4);
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(&body);
+    Some(frame)
+}
+
+/// Collects event fields into the record.
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl FieldVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        self.0.insert(field.name().to_owned(), value);
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
@@ -0,0 +1,261 @@
+use crate::error::MultipartError;
+
+/// Extracts the `boundary` parameter from a `multipart/form-data` content type.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_bot::multipart::boundary_from_content_type;
+///
+/// let content_type = "multipart/form-data; boundary=\"zalo-boundary\"";
+/// assert_eq!(boundary_from_content_type(content_type), Some("zalo-boundary"));
+/// assert_eq!(boundary_from_content_type("application/json"), None);
+/// ```
+#[must_use]
+pub fn boundary_from_content_type(content_type: &str) -> Option<&str> {
+    let mut segments = content_type.split(';');
+    let media_type = segments.next()?.trim();
+    if !media_type.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+
+    segments
+        .filter_map(|segment| segment.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+        .filter(|boundary| !boundary.is_empty())
+}
+
+/// A single part of a `multipart/form-data` body.
+///
+/// Header values and the body borrow from the original payload, so iterating
+/// parts never copies upload contents.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultipartPart<'a> {
+    headers: Vec<(&'a str, &'a str)>,
+    body: &'a [u8],
+}
+
+impl<'a> MultipartPart<'a> {
+    /// Returns the raw part headers in their original order.
+    #[must_use]
+    pub fn headers(&self) -> &[(&'a str, &'a str)] {
+        &self.headers
+    }
+
+    /// Returns the value of a header, matching the name case-insensitively.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns the form field name from `Content-Disposition`.
+    #[must_use]
+    pub fn name(&self) -> Option<&'a str> {
+        self.disposition_param("name")
+    }
+
+    /// Returns the uploaded file name from `Content-Disposition`, if any.
+    #[must_use]
+    pub fn filename(&self) -> Option<&'a str> {
+        self.disposition_param("filename")
+    }
+
+    /// Returns the part `Content-Type`, if any.
+    #[must_use]
+    pub fn content_type(&self) -> Option<&'a str> {
+        self.header("content-type")
+    }
+
+    /// Returns the raw part body.
+    #[must_use]
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    fn disposition_param(&self, param: &str) -> Option<&'a str> {
+        self.header("content-disposition")?
+            .split(';')
+            .skip(1)
+            .filter_map(|segment| segment.split_once('='))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(param))
+            .map(|(_, value)| value.trim().trim_matches('"'))
+    }
+}
+
+/// Lazy iterator over the parts of a `multipart/form-data` body.
+///
+/// Parts are located on demand; a structural error ends the iteration after
+/// being yielded once.
+#[derive(Clone, Debug)]
+pub struct MultipartParts<'a> {
+    rest: &'a [u8],
+    delimiter: Vec<u8>,
+    state: State,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    Start,
+    InBody,
+    Finished,
+}
+
+impl<'a> MultipartParts<'a> {
+    /// Creates an iterator over the body using the given boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::multipart::MultipartParts;
+    ///
+    /// let body = b"--b\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n--b--\r\n";
+    /// let part = MultipartParts::new(body, "b").next().unwrap()?;
+    /// assert_eq!(part.name(), Some("a"));
+    /// assert_eq!(part.body(), b"1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn new(body: &'a [u8], boundary: &str) -> Self {
+        let mut delimiter = Vec::with_capacity(boundary.len() + 2);
+        delimiter.extend_from_slice(b"--");
+        delimiter.extend_from_slice(boundary.as_bytes());
+
+        Self {
+            rest: body,
+            delimiter,
+            state: State::Start,
+        }
+    }
+
+    fn next_part(&mut self) -> Result<Option<MultipartPart<'a>>, MultipartError> {
+        if self.state == State::Start {
+            let start = find(self.rest, &self.delimiter).ok_or(MultipartError::MissingBoundary)?;
+            self.rest = &self.rest[start + self.delimiter.len()..];
+            self.state = State::InBody;
+        }
+
+        if self.rest.starts_with(b"--") {
+            return Ok(None);
+        }
+        let rest = self
+            .rest
+            .strip_prefix(b"\r\n")
+            .ok_or(MultipartError::MalformedPart)?;
+
+        let (header_block, body_start) = if rest.starts_with(b"\r\n") {
+            (&rest[..0], 2)
+        } else {
+            let end = find(rest, b"\r\n\r\n").ok_or(MultipartError::MalformedPart)?;
+            (&rest[..end], end + 4)
+        };
+        let headers = parse_headers(header_block)?;
+
+        let remainder = &rest[body_start..];
+        let mut closing = Vec::with_capacity(self.delimiter.len() + 2);
+        closing.extend_from_slice(b"\r\n");
+        closing.extend_from_slice(&self.delimiter);
+        let body_end = find(remainder, &closing).ok_or(MultipartError::MalformedPart)?;
+
+        self.rest = &remainder[body_end + closing.len()..];
+
+        Ok(Some(MultipartPart {
+            headers,
+            body: &remainder[..body_end],
+        }))
+    }
+}
+
+impl<'a> Iterator for MultipartParts<'a> {
+    type Item = Result<MultipartPart<'a>, MultipartError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == State::Finished {
+            return None;
+        }
+
+        match self.next_part() {
+            Ok(Some(part)) => Some(Ok(part)),
+            Ok(None) => {
+                self.state = State::Finished;
+                None
+            }
+            Err(error) => {
+                self.state = State::Finished;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+fn parse_headers(block: &[u8]) -> Result<Vec<(&str, &str)>, MultipartError> {
+    if block.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let block = std::str::from_utf8(block).map_err(|_| MultipartError::MalformedPart)?;
+    block
+        .split("\r\n")
+        .map(|line| {
+            line.split_once(':')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .ok_or(MultipartError::MalformedPart)
+        })
+        .collect()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &[u8] = b"preamble\r\n\
+--zalo\r\n\
+Content-Disposition: form-data; name=\"event_name\"\r\n\
+\r\n\
+user_send_file\r\n\
+--zalo\r\n\
+Content-Disposition: form-data; name=\"file\"; filename=\"report.pdf\"\r\n\
+Content-Type: application/pdf\r\n\
+\r\n\
+%PDF-1.7\r\n\
+--zalo--\r\n";
+
+    #[test]
+    fn iterates_parts_without_copying() {
+        let parts = MultipartParts::new(BODY, "zalo")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("well-formed body");
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name(), Some("event_name"));
+        assert_eq!(parts[0].body(), b"user_send_file");
+        assert_eq!(parts[1].filename(), Some("report.pdf"));
+        assert_eq!(parts[1].content_type(), Some("application/pdf"));
+        assert_eq!(parts[1].body(), b"%PDF-1.7");
+    }
+
+    #[test]
+    fn reports_missing_boundary_once() {
+        let mut parts = MultipartParts::new(BODY, "other");
+
+        assert_eq!(parts.next(), Some(Err(MultipartError::MissingBoundary)));
+        assert_eq!(parts.next(), None);
+    }
+
+    #[test]
+    fn reports_truncated_part() {
+        let body = b"--zalo\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nno closing";
+        let mut parts = MultipartParts::new(body, "zalo");
+
+        assert_eq!(parts.next(), Some(Err(MultipartError::MalformedPart)));
+    }
+}
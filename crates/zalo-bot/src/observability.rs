@@ -1,12 +1,39 @@
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
 use tracing::dispatcher::{self, Dispatch};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
-    fmt,
-    layer::{Layer, SubscriberExt},
+    field::{MakeVisitor, VisitFmt, VisitOutput},
+    fmt::{
+        self,
+        format::{DefaultVisitor, Writer},
+        writer::{BoxMakeWriter, MakeWriterExt, OrElse, WithMaxLevel},
+        FmtContext, FormatEvent, MakeWriter,
+    },
+    layer::{Context, Layer, SubscriberExt},
+    registry::LookupSpan,
     EnvFilter, Registry,
 };
-use zalo_types::{AppConfig, LogFormat};
+use zalo_types::{AppConfig, Environment, LogFormat, LogOutput, LoggingConfig, REDACTED};
 
+use crate::bunyan::BunyanFormat;
 use crate::error::{BotError, BotResult, ObservabilityError};
+use crate::json::{JsonFields, JsonFormat};
+#[cfg(feature = "msgpack")]
+use crate::msgpack::MsgPackLayer;
+
+/// File name prefix of daily-rolling log files.
+const LOG_FILE_PREFIX: &str = "zalo";
+
+/// File name suffix of daily-rolling log files.
+const LOG_FILE_SUFFIX: &str = "log";
 
 /// Builds a tracing dispatcher based on the runtime configuration.
 ///
@@ -29,7 +56,116 @@ use crate::error::{BotError, BotResult, ObservabilityError};
 /// # demo().expect("example executed");
 /// ```
 pub fn build_tracing_dispatch(config: &AppConfig) -> Result<Dispatch, ObservabilityError> {
-    let filter_expression = config.logging().filter().to_owned();
+    let format = config.logging().format();
+    build_dispatch(config, |writer, redaction| {
+        fmt_layer(format, writer, redaction)
+    })
+}
+
+/// Event formatter accepted by [`build_tracing_dispatch_with_formatter`].
+pub type BoxedEventFormat = Box<dyn FormatEvent<Registry, RedactingFields> + Send + Sync>;
+
+/// Builds a tracing dispatcher that renders events with a caller-provided
+/// formatter instead of the configured [`LogFormat`].
+///
+/// The filter, output streams and span timing still come from the
+/// configuration; only the shape of each line changes. Fields written through
+/// the context's [`RedactingFields`] (`ctx.field_format()`) are masked and
+/// sanitised like the built-in formats; a formatter that visits the event
+/// itself bypasses that redaction.
+///
+/// # Errors
+///
+/// Returns the same errors as [`build_tracing_dispatch`].
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write as _;
+///
+/// use tracing_subscriber::fmt::format::Writer;
+/// use tracing_subscriber::fmt::{FmtContext, FormatEvent};
+/// use tracing_subscriber::Registry;
+/// use zalo_bot::observability::{build_tracing_dispatch_with_formatter, RedactingFields};
+/// use zalo_types::AppConfig;
+///
+/// struct LevelOnly;
+///
+/// impl FormatEvent<Registry, RedactingFields> for LevelOnly {
+///     fn format_event(
+///         &self,
+///         _ctx: &FmtContext<'_, Registry, RedactingFields>,
+///         mut writer: Writer<'_>,
+///         event: &tracing::Event<'_>,
+///     ) -> std::fmt::Result {
+///         writeln!(writer, "{}", event.metadata().level())
+///     }
+/// }
+///
+/// let dispatch = build_tracing_dispatch_with_formatter(&AppConfig::default(), Box::new(LevelOnly))?;
+/// tracing::dispatcher::with_default(&dispatch, || tracing::info!("printed as INFO"));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn build_tracing_dispatch_with_formatter(
+    config: &AppConfig,
+    formatter: BoxedEventFormat,
+) -> Result<Dispatch, ObservabilityError> {
+    let formatter = SharedEventFormat(Arc::from(formatter));
+    build_dispatch(config, |writer, redaction| {
+        Ok(formatter_layer(&formatter, writer, redaction))
+    })
+}
+
+/// Creates an output layer rendering events with `formatter` and fields with
+/// `redaction`.
+fn formatter_layer<W>(
+    formatter: &SharedEventFormat,
+    writer: W,
+    redaction: &FieldRedaction,
+) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    fmt::layer()
+        .fmt_fields(RedactingFields {
+            redaction: redaction.clone(),
+        })
+        .event_format(formatter.clone())
+        .with_writer(writer)
+        .boxed()
+}
+
+/// Assembles the subscriber, creating one output layer per configured sink
+/// with `layer`.
+///
+/// A [`PiiFilterLayer`] for the configured environment drops marked events;
+/// its masking rules, together with the default [`SecretSanitizer`], are
+/// handed to `layer`.
+fn build_dispatch<L>(config: &AppConfig, layer: L) -> Result<Dispatch, ObservabilityError>
+where
+    L: Fn(
+        BoxMakeWriter,
+        &FieldRedaction,
+    ) -> Result<Box<dyn Layer<Registry> + Send + Sync>, ObservabilityError>,
+{
+    build_dispatch_to(config, io::stdout, layer)
+}
+
+/// Implements [`build_dispatch`] with `stdout` standing in for the standard
+/// output stream.
+fn build_dispatch_to<O, L>(
+    config: &AppConfig,
+    stdout: O,
+    layer: L,
+) -> Result<Dispatch, ObservabilityError>
+where
+    O: for<'a> MakeWriter<'a> + Clone + Send + Sync + 'static,
+    L: Fn(
+        BoxMakeWriter,
+        &FieldRedaction,
+    ) -> Result<Box<dyn Layer<Registry> + Send + Sync>, ObservabilityError>,
+{
+    let filter_expression = config.effective_filter();
     let filter = EnvFilter::try_new(filter_expression.clone()).map_err(|source| {
         ObservabilityError::InvalidFilter {
             filter: filter_expression,
@@ -37,14 +173,205 @@ pub fn build_tracing_dispatch(config: &AppConfig) -> Result<Dispatch, Observabil
         }
     })?;
 
-    let fmt_layer = match config.logging().format() {
-        LogFormat::Json => fmt::layer().json().boxed(),
-        LogFormat::Text => fmt::layer().boxed(),
+    let pii = PiiFilterLayer::new(config.environment());
+    let redaction = pii
+        .field_redaction()
+        .with_secrets(SecretSanitizer::default());
+    let layer = |writer| layer(writer, &redaction);
+    let output_layer = match config.logging().output() {
+        LogOutput::Stdout => layer(BoxMakeWriter::new(stdout))?,
+        LogOutput::Split => layer(BoxMakeWriter::new(split_writer(stdout, io::stderr)))?,
+        LogOutput::Both => {
+            let directory = config
+                .logging()
+                .directory()
+                .ok_or(ObservabilityError::MissingDirectory)?;
+            let file_writer = daily_file_writer(directory)?;
+            layer(BoxMakeWriter::new(stdout))?
+                .and_then(layer(BoxMakeWriter::new(file_writer))?)
+                .boxed()
+        }
+        LogOutput::Console => console_layer(&redaction)?,
+    };
+
+    // The effective filter already lowers directives above the environment's
+    // ceiling; compare against the configured one to tell whether it did.
+    let requested = config.logging().filter().trim();
+    let clamped = config.max_level_for_environment().filter(|ceiling| {
+        EnvFilter::try_new(requested)
+            .ok()
+            .and_then(|requested| requested.max_level_hint())
+            .is_some_and(|requested| requested > *ceiling)
+    });
+
+    // The filter is a global layer, so it applies to the output layer below
+    // it as well.
+    let timing_layer = config.logging().span_timing().then(TimingLayer::new);
+    let subscriber = Registry::default()
+        .with(output_layer)
+        .with(filter)
+        .with(pii)
+        .with(timing_layer);
+    let dispatch = Dispatch::new(subscriber);
+
+    if let Some(ceiling) = clamped {
+        dispatcher::with_default(&dispatch, || {
+            tracing::warn!(
+                filter = requested,
+                effective_filter = %config.effective_filter(),
+                environment = config.environment().as_str(),
+                %ceiling,
+                "log filter is more verbose than the environment allows, clamping"
+            );
+        });
+    }
+
+    Ok(dispatch)
+}
+
+/// Creates the layer behind [`LogOutput::Console`].
+///
+/// The browser console timestamps records itself, so they are rendered as
+/// plain text without a time; the configured format and any custom formatter
+/// do not apply. Fields pass through `redaction` as in every other output.
+#[cfg(feature = "wasm")]
+fn console_layer(
+    redaction: &FieldRedaction,
+) -> Result<Box<dyn Layer<Registry> + Send + Sync>, ObservabilityError> {
+    Ok(console_text_layer(crate::console::ConsoleWriter, redaction))
+}
+
+#[cfg(not(feature = "wasm"))]
+fn console_layer(
+    _redaction: &FieldRedaction,
+) -> Result<Box<dyn Layer<Registry> + Send + Sync>, ObservabilityError> {
+    Err(ObservabilityError::ConsoleUnavailable)
+}
+
+/// Creates the text layer used for console output, writing to `writer`.
+#[cfg(any(test, feature = "wasm"))]
+fn console_text_layer<W>(
+    writer: W,
+    redaction: &FieldRedaction,
+) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    fmt::layer()
+        .without_time()
+        .with_ansi(false)
+        .fmt_fields(RedactingFields {
+            redaction: redaction.clone(),
+        })
+        .with_writer(writer)
+        .boxed()
+}
+
+/// Cloneable handle letting one [`BoxedEventFormat`] serve several output
+/// layers.
+#[derive(Clone)]
+struct SharedEventFormat(Arc<dyn FormatEvent<Registry, RedactingFields> + Send + Sync>);
+
+impl FormatEvent<Registry, RedactingFields> for SharedEventFormat {
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, Registry, RedactingFields>,
+        writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        self.0.format_event(ctx, writer, event)
+    }
+}
+
+/// Combines two writers so that `WARN` and `ERROR` records go to `stderr` and
+/// everything else goes to `stdout`.
+///
+/// This is the writer behind [`LogOutput::Split`]; it is exposed so callers
+/// can route the streams to custom sinks.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_bot::observability::split_writer;
+///
+/// let writer = split_writer(std::io::stdout, std::io::stderr);
+/// let subscriber = tracing_subscriber::fmt().with_writer(writer).finish();
+/// tracing::subscriber::with_default(subscriber, || tracing::warn!("to stderr"));
+/// ```
+pub fn split_writer<O, E>(stdout: O, stderr: E) -> OrElse<WithMaxLevel<E>, O>
+where
+    O: for<'a> MakeWriter<'a>,
+    E: for<'a> MakeWriter<'a>,
+{
+    stderr.with_max_level(Level::WARN).or_else(stdout)
+}
+
+/// Creates a writer appending to `zalo.<date>.log` files in `directory`,
+/// starting a new file every day.
+fn daily_file_writer(directory: &Path) -> Result<RollingFileAppender, ObservabilityError> {
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .build(directory)
+        .map_err(|source| ObservabilityError::FileOutput {
+            directory: directory.to_path_buf(),
+            source,
+        })
+}
+
+fn fmt_layer<S, W>(
+    format: LogFormat,
+    writer: W,
+    redaction: &FieldRedaction,
+) -> Result<Box<dyn Layer<S> + Send + Sync>, ObservabilityError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let layer = match format {
+        LogFormat::Json => fmt::layer()
+            .fmt_fields(JsonFields::new(redaction.clone()))
+            .event_format(JsonFormat::new(redaction.clone()))
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Text => fmt::layer()
+            .fmt_fields(RedactingFields {
+                redaction: redaction.clone(),
+            })
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Bunyan => fmt::layer()
+            .event_format(BunyanFormat::new(redaction.clone()))
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::MsgPack => return msgpack_layer(writer, redaction),
     };
 
-    let subscriber = Registry::default().with(filter).with(fmt_layer);
+    Ok(layer)
+}
+
+#[cfg(feature = "msgpack")]
+fn msgpack_layer<S, W>(
+    writer: W,
+    redaction: &FieldRedaction,
+) -> Result<Box<dyn Layer<S> + Send + Sync>, ObservabilityError>
+where
+    S: Subscriber,
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    Ok(MsgPackLayer::new(writer, redaction.clone()).boxed())
+}
 
-    Ok(Dispatch::new(subscriber))
+#[cfg(not(feature = "msgpack"))]
+fn msgpack_layer<S, W>(
+    _writer: W,
+    _redaction: &FieldRedaction,
+) -> Result<Box<dyn Layer<S> + Send + Sync>, ObservabilityError>
+where
+    S: Subscriber,
+{
+    Err(ObservabilityError::MsgPackUnavailable)
 }
 
 /// Installs the global tracing subscriber according to the configuration.
@@ -80,11 +407,823 @@ pub fn init_tracing(config: &AppConfig) -> BotResult<()> {
     Ok(())
 }
 
+/// Installs the global tracing subscriber unless one is already installed.
+///
+/// This is the entry point for libraries embedded in a host that may own the
+/// process-wide subscriber: when one is already set, a single `DEBUG` note is
+/// emitted through it and the call succeeds without building a dispatcher,
+/// so the configuration is not even checked. Applications that own the
+/// process should prefer [`init_tracing`], which reports that case as an
+/// error.
+///
+/// # Errors
+///
+/// Returns [`BotError::Observability`] when no subscriber is installed and
+/// the dispatcher cannot be built, for example because the filter expression
+/// is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_bot::observability::init_tracing_or_warn;
+/// use zalo_types::AppConfig;
+///
+/// init_tracing_or_warn(&AppConfig::default())?;
+/// init_tracing_or_warn(&AppConfig::default())?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn init_tracing_or_warn(config: &AppConfig) -> BotResult<()> {
+    if dispatcher::has_been_set() {
+        tracing::debug!("global tracing subscriber already installed, keeping it");
+        return Ok(());
+    }
+    let dispatch = build_tracing_dispatch(config)?;
+    // Another thread may have installed a subscriber in the meantime.
+    if dispatcher::set_global_default(dispatch).is_err() {
+        tracing::debug!("global tracing subscriber already installed, keeping it");
+    }
+
+    Ok(())
+}
+
+/// Installs the global tracing subscriber from a filter expression and a
+/// format, without building an [`AppConfig`] first.
+///
+/// This is a shortcut for scripts and tests: it logs to standard output with
+/// every other setting at its default, then behaves like [`init_tracing`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`init_tracing`], including when the filter
+/// expression is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_bot::observability::init_from_filter;
+/// use zalo_types::LogFormat;
+///
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// if tracing::dispatcher::has_been_set() {
+///     return Ok(());
+/// }
+/// init_from_filter("info,zalo_bot=debug", LogFormat::Text)?;
+/// tracing::debug!("subscriber installed");
+/// # Ok(())
+/// # }
+/// # demo().expect("example executed");
+/// ```
+pub fn init_from_filter(filter: &str, format: LogFormat) -> BotResult<()> {
+    let config = AppConfig::default().with_logging(LoggingConfig::new(filter, format));
+    init_tracing(&config)
+}
+
+/// Length of the sliding window used by [`ErrorRateLayer`], in seconds.
+const ERROR_RATE_WINDOW_SECS: u64 = 60;
+
+/// Marker for a bucket that has never been written.
+const EMPTY_BUCKET: u64 = u64::MAX;
+
+/// Source of the current time for [`ErrorRateLayer`] and [`TimingLayer`].
+///
+/// The layers use [`SystemClock`] by default; tests inject a manual clock to
+/// move time forward deterministically.
+pub trait Clock: Send + Sync {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+}
+
+/// [`Clock`] backed by [`Instant::now`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Layer counting events per level over the last minute.
+///
+/// Counts live in one-second buckets of atomic counters, so recording an
+/// event never blocks. Clones share the counters: keep one clone for the
+/// readiness check and install the other in the subscriber. Events that race
+/// with a bucket rolling over may be dropped, which is acceptable for health
+/// reporting.
+///
+/// # Examples
+///
+/// ```
+/// use tracing_subscriber::layer::SubscriberExt;
+/// use zalo_bot::observability::ErrorRateLayer;
+///
+/// let layer = ErrorRateLayer::new();
+/// let subscriber = tracing_subscriber::registry().with(layer.clone());
+/// tracing::subscriber::with_default(subscriber, || tracing::error!("boom"));
+/// assert_eq!(layer.snapshot().errors_last_minute(), 1);
+/// ```
+#[derive(Clone)]
+pub struct ErrorRateLayer {
+    inner: Arc<ErrorRateInner>,
+}
+
+struct ErrorRateInner {
+    clock: Box<dyn Clock>,
+    origin: Instant,
+    buckets: Vec<Bucket>,
+}
+
+struct Bucket {
+    second: AtomicU64,
+    counts: [AtomicU64; LEVELS],
+}
+
+/// Number of tracing levels tracked by [`ErrorRateLayer`].
+const LEVELS: usize = 5;
+
+impl ErrorRateLayer {
+    /// Creates a layer reading time from [`SystemClock`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Creates a layer reading time from the provided clock.
+    #[must_use]
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        let origin = clock.now();
+        let buckets = (0..ERROR_RATE_WINDOW_SECS)
+            .map(|_| Bucket {
+                second: AtomicU64::new(EMPTY_BUCKET),
+                counts: Default::default(),
+            })
+            .collect();
+
+        Self {
+            inner: Arc::new(ErrorRateInner {
+                clock: Box::new(clock),
+                origin,
+                buckets,
+            }),
+        }
+    }
+
+    /// Returns the event counts recorded during the last minute.
+    #[must_use]
+    pub fn snapshot(&self) -> ErrorRateSnapshot {
+        let now = self.inner.elapsed_secs();
+        let mut counts = [0_u64; LEVELS];
+        for bucket in &self.inner.buckets {
+            let second = bucket.second.load(Ordering::Acquire);
+            if second == EMPTY_BUCKET || now.saturating_sub(second) >= ERROR_RATE_WINDOW_SECS {
+                continue;
+            }
+            for (total, count) in counts.iter_mut().zip(&bucket.counts) {
+                *total += count.load(Ordering::Relaxed);
+            }
+        }
+
+        ErrorRateSnapshot { counts }
+    }
+
+    fn record(&self, level: Level) {
+        let now = self.inner.elapsed_secs();
+        let bucket = &self.inner.buckets[(now % ERROR_RATE_WINDOW_SECS) as usize];
+        let second = bucket.second.load(Ordering::Acquire);
+        if second != now
+            && bucket
+                .second
+                .compare_exchange(second, now, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            for count in &bucket.counts {
+                count.store(0, Ordering::Relaxed);
+            }
+        }
+        bucket.counts[level_index(level)].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for ErrorRateLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ErrorRateLayer {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("ErrorRateLayer")
+            .field("snapshot", &self.snapshot())
+            .finish()
+    }
+}
+
+impl ErrorRateInner {
+    fn elapsed_secs(&self) -> u64 {
+        self.clock
+            .now()
+            .saturating_duration_since(self.origin)
+            .as_secs()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ErrorRateLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        self.record(*event.metadata().level());
+    }
+}
+
+/// Event counts captured by [`ErrorRateLayer::snapshot`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ErrorRateSnapshot {
+    counts: [u64; LEVELS],
+}
+
+impl ErrorRateSnapshot {
+    /// Returns the number of events at `level` during the last minute.
+    #[must_use]
+    pub fn count(&self, level: Level) -> u64 {
+        self.counts[level_index(level)]
+    }
+
+    /// Returns the number of `ERROR` events during the last minute.
+    #[must_use]
+    pub fn errors_last_minute(&self) -> u64 {
+        self.count(Level::ERROR)
+    }
+
+    /// Returns the number of `WARN` events during the last minute.
+    #[must_use]
+    pub fn warnings_last_minute(&self) -> u64 {
+        self.count(Level::WARN)
+    }
+}
+
+fn level_index(level: Level) -> usize {
+    match level {
+        Level::TRACE => 0,
+        Level::DEBUG => 1,
+        Level::INFO => 2,
+        Level::WARN => 3,
+        Level::ERROR => 4,
+    }
+}
+
+/// Target of the events emitted by [`TimingLayer`].
+pub const TIMING_TARGET: &str = "zalo::timing";
+
+/// Layer logging how long each span stayed open.
+///
+/// When a span closes, the layer emits an `INFO` event with target
+/// [`TIMING_TARGET`] carrying the span name as `span` and `elapsed_ms`, which gives
+/// cheap latency numbers for operations such as OA sends without an OTLP
+/// exporter. [`build_tracing_dispatch`] installs it when
+/// `logging.span_timing` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use tracing_subscriber::layer::SubscriberExt;
+/// use zalo_bot::observability::TimingLayer;
+///
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer())
+///     .with(TimingLayer::new());
+/// tracing::subscriber::with_default(subscriber, || {
+///     let _span = tracing::info_span!("oa.send").entered();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct TimingLayer {
+    clock: Arc<dyn Clock>,
+}
+
+/// Instant at which a span was created, stored in its extensions.
+struct SpanStart(Instant);
+
+impl TimingLayer {
+    /// Creates a layer reading time from [`SystemClock`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Creates a layer reading time from the provided clock.
+    #[must_use]
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Arc::new(clock),
+        }
+    }
+}
+
+impl Default for TimingLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for TimingLayer {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("TimingLayer")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanStart(self.clock.now()));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let name = span.name();
+        let Some(start) = span.extensions().get::<SpanStart>().map(|start| start.0) else {
+            return;
+        };
+        // Release the span before emitting, the event is dispatched through
+        // the same registry.
+        drop(span);
+
+        let elapsed = self.clock.now().saturating_duration_since(start);
+        let elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        tracing::info!(target: TIMING_TARGET, span = name, elapsed_ms, "operation completed");
+    }
+}
+
+/// Field marking an event as carrying personal data.
+pub const PII_MARKER_FIELD: &str = "pii";
+
+/// Field names masked by [`PiiFilterLayer`] unless configured otherwise.
+pub const DEFAULT_PII_FIELDS: [&str; 2] = ["phone", "email"];
+
+/// Layer keeping personal data out of production logs.
+///
+/// In [`Environment::Production`] the layer drops every event carrying
+/// [`PII_MARKER_FIELD`]` = true`, and the values of the configured fields are
+/// masked, keeping only their last two characters. In the other environments
+/// both are pass-throughs, so the data stays available while debugging.
+///
+/// [`build_tracing_dispatch`] installs the layer with the default fields and
+/// masks them in every built-in [`LogFormat`]. Layers cannot rewrite the
+/// events seen by other layers, so a hand-assembled subscriber has to install
+/// the formatter returned by [`field_formatter`](Self::field_formatter) in its
+/// output layer as well.
+///
+/// # Examples
+///
+/// ```
+/// use tracing_subscriber::layer::SubscriberExt;
+/// use zalo_bot::observability::PiiFilterLayer;
+/// use zalo_types::Environment;
+///
+/// let pii = PiiFilterLayer::new(Environment::Production);
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().fmt_fields(pii.field_formatter()))
+///     .with(pii);
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(phone = "0901234567", "user registered");
+///     tracing::info!(pii = true, "dropped in production");
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct PiiFilterLayer {
+    fields: Arc<[String]>,
+    enforced: bool,
+}
+
+impl PiiFilterLayer {
+    /// Creates a layer masking [`DEFAULT_PII_FIELDS`], enforced in
+    /// production only.
+    #[must_use]
+    pub fn new(environment: Environment) -> Self {
+        Self {
+            fields: DEFAULT_PII_FIELDS.iter().map(ToString::to_string).collect(),
+            enforced: environment == Environment::Production,
+        }
+    }
+
+    /// Replaces the names of the fields whose values are masked.
+    #[must_use]
+    pub fn with_fields<I, F>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<String>,
+    {
+        self.fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the names of the fields whose values are masked.
+    #[must_use]
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Returns whether events are dropped and fields masked.
+    #[must_use]
+    pub fn is_enforced(&self) -> bool {
+        self.enforced
+    }
+
+    /// Returns the field formatter masking the configured fields, for use
+    /// with [`fmt::Layer::fmt_fields`].
+    #[must_use]
+    pub fn field_formatter(&self) -> RedactingFields {
+        RedactingFields {
+            redaction: self.field_redaction(),
+        }
+    }
+
+    /// Returns the masking rules applied by the built-in output formats.
+    pub(crate) fn field_redaction(&self) -> FieldRedaction {
+        FieldRedaction {
+            pii_fields: self.enforced.then(|| Arc::clone(&self.fields)),
+            secrets: None,
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for PiiFilterLayer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        if !self.enforced {
+            return true;
+        }
+        let mut marker = PiiMarker(false);
+        event.record(&mut marker);
+        !marker.0
+    }
+}
+
+/// Visitor detecting [`PII_MARKER_FIELD`]` = true`.
+struct PiiMarker(bool);
+
+impl Visit for PiiMarker {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == PII_MARKER_FIELD {
+            self.0 |= value;
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+/// Field rewriting applied by the built-in output formats.
+///
+/// Combines the masking rules of a [`PiiFilterLayer`] and a
+/// [`SecretSanitizer`]; the text, JSON, Bunyan and MessagePack formats all run
+/// event and span fields through [`RedactingVisitor`] with the same rules.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FieldRedaction {
+    pii_fields: Option<Arc<[String]>>,
+    secrets: Option<SecretSanitizer>,
+}
+
+impl FieldRedaction {
+    /// Wraps `inner` so that it receives rewritten values.
+    pub(crate) fn visitor<V>(&self, inner: V) -> RedactingVisitor<V> {
+        RedactingVisitor {
+            inner,
+            redaction: self.clone(),
+        }
+    }
+
+    /// Adds the rules of `secrets`, which take precedence over PII masking.
+    pub(crate) fn with_secrets(mut self, secrets: SecretSanitizer) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    fn is_secret(&self, name: &str) -> bool {
+        self.secrets
+            .as_ref()
+            .is_some_and(|secrets| secrets.is_secret(name))
+    }
+
+    /// Returns whether the value of the field `name` is rewritten.
+    fn covers(&self, name: &str) -> bool {
+        self.is_secret(name)
+            || self
+                .pii_fields
+                .as_deref()
+                .is_some_and(|fields| fields.iter().any(|field| field == name))
+    }
+
+    /// Returns the replacement for the value of the field `name`.
+    fn rewrite(&self, name: &str, value: &str) -> String {
+        if self.is_secret(name) {
+            REDACTED.to_owned()
+        } else {
+            mask_value(value)
+        }
+    }
+}
+
+/// Field formatter produced by [`PiiFilterLayer::field_formatter`] and
+/// [`SecretSanitizer::field_formatter`].
+///
+/// Renders fields like [`DefaultFields`](tracing_subscriber::fmt::format::DefaultFields), masking the configured ones.
+#[derive(Clone, Debug)]
+pub struct RedactingFields {
+    redaction: FieldRedaction,
+}
+
+impl<'a> MakeVisitor<Writer<'a>> for RedactingFields {
+    type Visitor = RedactingVisitor<DefaultVisitor<'a>>;
+
+    fn make_visitor(&self, target: Writer<'a>) -> Self::Visitor {
+        self.redaction.visitor(DefaultVisitor::new(target, true))
+    }
+}
+
+/// Visitor forwarding fields to `V`, with masked fields replaced by their
+/// rewritten value as a string.
+#[derive(Debug)]
+pub struct RedactingVisitor<V> {
+    inner: V,
+    redaction: FieldRedaction,
+}
+
+impl<V: Visit> RedactingVisitor<V> {
+    /// Records the rewritten form of `value` when the field is masked,
+    /// returning whether it was.
+    fn record_masked(&mut self, field: &Field, value: &dyn std::fmt::Display) -> bool {
+        if !self.redaction.covers(field.name()) {
+            return false;
+        }
+        let masked = self.redaction.rewrite(field.name(), &value.to_string());
+        self.inner.record_str(field, &masked);
+        true
+    }
+}
+
+impl<V: Visit> Visit for RedactingVisitor<V> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if !self.record_masked(field, &value) {
+            self.inner.record_f64(field, value);
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if !self.record_masked(field, &value) {
+            self.inner.record_i64(field, value);
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if !self.record_masked(field, &value) {
+            self.inner.record_u64(field, value);
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if !self.record_masked(field, &value) {
+            self.inner.record_bool(field, value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if !self.record_masked(field, &value) {
+            self.inner.record_str(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.record_masked(field, &format_args!("{value:?}")) {
+            self.inner.record_debug(field, value);
+        }
+    }
+}
+
+impl<V: VisitOutput<std::fmt::Result> + Visit> VisitOutput<std::fmt::Result>
+    for RedactingVisitor<V>
+{
+    fn finish(self) -> std::fmt::Result {
+        self.inner.finish()
+    }
+}
+
+impl<V: VisitFmt> VisitFmt for RedactingVisitor<V> {
+    fn writer(&mut self) -> &mut dyn std::fmt::Write {
+        self.inner.writer()
+    }
+}
+
+/// Replaces every character but the last two with `*`; shorter values are
+/// masked entirely.
+fn mask_value(value: &str) -> String {
+    let len = value.chars().count();
+    let visible = if len > 4 { 2 } else { 0 };
+    value
+        .chars()
+        .enumerate()
+        .map(|(index, ch)| if index < len - visible { '*' } else { ch })
+        .collect()
+}
+
+/// Field-name patterns masked by [`SecretSanitizer::default`].
+pub const DEFAULT_SECRET_PATTERNS: [&str; 3] = ["*secret*", "*token*", "authorization"];
+
+/// Rules replacing the values of secret-looking fields with [`REDACTED`], in
+/// span and event fields alike.
+///
+/// Patterns match field names case-insensitively; `*` matches any run of
+/// characters, so `*token*` covers `access_token` and `refresh_token`. This
+/// is a safety net for secrets recorded by mistake and applies in every
+/// environment. [`build_tracing_dispatch`] applies the default patterns in
+/// every built-in [`LogFormat`]; a hand-assembled subscriber installs
+/// [`field_formatter`](Self::field_formatter) in its output layer.
+///
+/// # Examples
+///
+/// ```
+/// use tracing_subscriber::layer::SubscriberExt;
+/// use zalo_bot::observability::SecretSanitizer;
+///
+/// let sanitizer = SecretSanitizer::default();
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().fmt_fields(sanitizer.field_formatter()));
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(access_token = "abc123", "token refreshed");
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct SecretSanitizer {
+    patterns: Arc<[String]>,
+}
+
+impl SecretSanitizer {
+    /// Creates a sanitizer masking fields whose names match any of
+    /// `patterns`.
+    #[must_use]
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|pattern| pattern.into().to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Returns the field-name patterns, lowercased.
+    #[must_use]
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Returns whether the value of the field `name` is masked.
+    #[must_use]
+    pub fn is_secret(&self, name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        self.patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern, &name))
+    }
+
+    /// Returns the field formatter masking secret fields, for use with
+    /// [`fmt::Layer::fmt_fields`].
+    #[must_use]
+    pub fn field_formatter(&self) -> RedactingFields {
+        RedactingFields {
+            redaction: FieldRedaction::default().with_secrets(self.clone()),
+        }
+    }
+}
+
+impl Default for SecretSanitizer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SECRET_PATTERNS)
+    }
+}
+
+/// Matches `name` against `pattern`, where `*` stands for any run of
+/// characters.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let mut segments = pattern.split('*');
+    let first = segments.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut segments: Vec<&str> = segments.collect();
+    let Some(last) = segments.pop() else {
+        return rest.is_empty();
+    };
+    for segment in segments {
+        match rest.find(segment) {
+            Some(index) => rest = &rest[index + segment.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use zalo_types::{AppError, AppErrorKind, LoggingConfig};
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::fmt::FormatFields;
+    use zalo_types::{AppError, AppErrorKind};
+
+    /// In-memory writer shared between the test and the subscriber.
+    #[derive(Clone, Default)]
+    struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl CaptureWriter {
+        fn contents(&self) -> String {
+            let buffer = self.0.lock().expect("lock poisoned");
+            String::from_utf8_lossy(&buffer).into_owned()
+        }
+    }
+
+    impl io::Write for CaptureWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("lock poisoned").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CaptureWriter {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// Writer recording every `write` call as a separate chunk.
+    #[derive(Clone, Default)]
+    struct ChunkWriter(Arc<Mutex<Vec<Vec<u8>>>>);
+
+    impl io::Write for ChunkWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("lock poisoned").push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for ChunkWriter {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn json_records_are_written_whole_under_concurrency() {
+        let sink = ChunkWriter::default();
+        let layer =
+            fmt_layer(LogFormat::Json, sink.clone(), &FieldRedaction::default()).expect("layer");
+        let dispatch = Dispatch::new(Registry::default().with(layer));
+
+        std::thread::scope(|scope| {
+            for worker in 0..8 {
+                let dispatch = dispatch.clone();
+                let padding = "x".repeat(512);
+                scope.spawn(move || {
+                    tracing::dispatcher::with_default(&dispatch, || {
+                        for sequence in 0..50 {
+                            tracing::info!(worker, sequence, padding = padding.as_str(), "tick");
+                        }
+                    });
+                });
+            }
+        });
+
+        let chunks = sink.0.lock().expect("lock poisoned");
+        assert_eq!(chunks.len(), 8 * 50);
+        for chunk in chunks.iter() {
+            let line = std::str::from_utf8(chunk).expect("utf-8 record");
+            assert_eq!(line.matches('\n').count(), 1);
+            let record: serde_json::Value =
+                serde_json::from_str(line.trim_end()).expect("complete json record");
+            assert_eq!(record["fields"]["message"], "tick");
+        }
+    }
 
     #[test]
     fn builds_dispatcher_for_text_logs() {
@@ -96,6 +1235,452 @@ mod tests {
         });
     }
 
+    #[test]
+    fn production_clamps_verbose_filter_to_ceiling() {
+        for (environment, trace_enabled) in [
+            (Environment::Production, false),
+            (Environment::Development, true),
+        ] {
+            let config = AppConfig::default()
+                .with_environment(environment)
+                .with_logging(LoggingConfig::new("trace", LogFormat::Json));
+            let dispatch = build_tracing_dispatch(&config).expect("dispatcher");
+
+            let (trace, debug) = tracing::dispatcher::with_default(&dispatch, || {
+                (
+                    tracing::enabled!(Level::TRACE),
+                    tracing::enabled!(Level::DEBUG),
+                )
+            });
+            assert_eq!(trace, trace_enabled, "{environment:?}");
+            assert!(debug, "{environment:?}");
+        }
+    }
+
+    #[test]
+    fn split_writer_routes_by_level() {
+        for format in [LogFormat::Text, LogFormat::Json] {
+            let stdout = CaptureWriter::default();
+            let stderr = CaptureWriter::default();
+            let writer = split_writer(stdout.clone(), stderr.clone());
+            let layer = fmt_layer(format, writer, &FieldRedaction::default()).expect("layer");
+            let subscriber = Registry::default().with(layer);
+
+            tracing::subscriber::with_default(subscriber, || {
+                tracing::error!("disk on fire");
+                tracing::info!("all good");
+            });
+
+            assert!(stderr.contents().contains("disk on fire"));
+            assert!(!stderr.contents().contains("all good"));
+            assert!(stdout.contents().contains("all good"));
+            assert!(!stdout.contents().contains("disk on fire"));
+        }
+    }
+
+    #[test]
+    fn bunyan_format_emits_bunyan_records() {
+        let sink = CaptureWriter::default();
+        let subscriber = Registry::default().with(
+            fmt_layer(LogFormat::Bunyan, sink.clone(), &FieldRedaction::default()).expect("layer"),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(user_id = "u-1", "quota low");
+        });
+
+        let record: serde_json::Value =
+            serde_json::from_str(sink.contents().trim_end()).expect("bunyan json line");
+        assert_eq!(record["v"], 0);
+        assert_eq!(record["level"], 40);
+        assert_eq!(record["msg"], "quota low");
+        assert_eq!(record["user_id"], "u-1");
+        assert_eq!(record["pid"], std::process::id());
+        assert!(record["time"]
+            .as_str()
+            .is_some_and(|time| time.ends_with('Z')));
+        assert!(record["hostname"].is_string());
+        assert!(record["name"].is_string());
+    }
+
+    #[test]
+    fn file_output_preset_writes_log_files() {
+        let directory = tempfile::tempdir().expect("temp dir");
+        let logging =
+            LoggingConfig::new("info", LogFormat::Text).with_file_output(directory.path());
+        let config = AppConfig::default().with_logging(logging);
+        let stdout = CaptureWriter::default();
+        let dispatch = build_dispatch_to(&config, stdout.clone(), |writer, redaction| {
+            fmt_layer(LogFormat::Text, writer, redaction)
+        })
+        .expect("dispatcher");
+
+        tracing::dispatcher::with_default(&dispatch, || {
+            tracing::info!("written to both sinks");
+        });
+
+        assert!(stdout.contents().contains("written to both sinks"));
+        let files = std::fs::read_dir(directory.path())
+            .expect("read log dir")
+            .map(|entry| entry.expect("dir entry").path())
+            .collect::<Vec<_>>();
+        assert_eq!(files.len(), 1);
+        let contents = std::fs::read_to_string(&files[0]).expect("read log file");
+        assert!(contents.contains("written to both sinks"));
+    }
+
+    /// Clock advanced manually by the test.
+    #[derive(Clone)]
+    struct ManualClock(Arc<Mutex<Instant>>);
+
+    impl ManualClock {
+        fn advance(&self, duration: std::time::Duration) {
+            *self.0.lock().expect("lock poisoned") += duration;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().expect("lock poisoned")
+        }
+    }
+
+    #[test]
+    fn error_rate_layer_counts_and_expires_events() {
+        let clock = ManualClock(Arc::new(Mutex::new(Instant::now())));
+        let layer = ErrorRateLayer::with_clock(clock.clone());
+        let subscriber = Registry::default().with(layer.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::error!("first");
+            tracing::warn!("slow");
+            clock.advance(std::time::Duration::from_secs(30));
+            tracing::error!("second");
+        });
+
+        let snapshot = layer.snapshot();
+        assert_eq!(snapshot.errors_last_minute(), 2);
+        assert_eq!(snapshot.warnings_last_minute(), 1);
+
+        clock.advance(std::time::Duration::from_secs(31));
+        assert_eq!(layer.snapshot().errors_last_minute(), 1);
+
+        clock.advance(std::time::Duration::from_secs(30));
+        assert_eq!(layer.snapshot(), ErrorRateSnapshot::default());
+    }
+
+    #[test]
+    fn timing_layer_reports_elapsed_time_on_close() {
+        let clock = ManualClock(Arc::new(Mutex::new(Instant::now())));
+        let sink = CaptureWriter::default();
+        let subscriber = Registry::default()
+            .with(
+                fmt_layer(LogFormat::Json, sink.clone(), &FieldRedaction::default())
+                    .expect("layer"),
+            )
+            .with(TimingLayer::with_clock(clock.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("oa.send");
+            span.in_scope(|| clock.advance(std::time::Duration::from_millis(42)));
+        });
+
+        let record: serde_json::Value =
+            serde_json::from_str(sink.contents().trim_end()).expect("timing json line");
+        assert_eq!(record["target"], TIMING_TARGET);
+        assert_eq!(record["fields"]["span"], "oa.send");
+        assert_eq!(record["fields"]["elapsed_ms"], 42);
+    }
+
+    fn pii_output(environment: Environment) -> String {
+        let sink = CaptureWriter::default();
+        let pii = PiiFilterLayer::new(environment);
+        let subscriber = Registry::default()
+            .with(
+                fmt::layer()
+                    .fmt_fields(pii.field_formatter())
+                    .with_ansi(false)
+                    .with_writer(sink.clone()),
+            )
+            .with(pii);
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(phone = "0901234567", user = "u-1", "registered");
+            tracing::info!(pii = true, "address: 1 Le Loi");
+        });
+        sink.contents()
+    }
+
+    #[test]
+    fn pii_filter_masks_fields_only_in_production() {
+        let production = pii_output(Environment::Production);
+        assert!(production.contains("phone=\"********67\""), "{production}");
+        assert!(production.contains("user=\"u-1\""));
+        assert!(!production.contains("0901234567"));
+        assert!(!production.contains("Le Loi"));
+
+        let development = pii_output(Environment::Development);
+        assert!(
+            development.contains("phone=\"0901234567\""),
+            "{development}"
+        );
+        assert!(development.contains("Le Loi"));
+    }
+
+    #[test]
+    fn dispatch_masks_pii_in_json_logs() {
+        let directory = tempfile::tempdir().expect("temp dir");
+        let logging =
+            LoggingConfig::new("info", LogFormat::Json).with_file_output(directory.path());
+        let config = AppConfig::default()
+            .with_environment(Environment::Production)
+            .with_logging(logging);
+        let dispatch = build_tracing_dispatch(&config).expect("dispatcher");
+
+        tracing::dispatcher::with_default(&dispatch, || {
+            let span = tracing::info_span!("signup", email = "an@example.com");
+            span.in_scope(|| {
+                tracing::info!(phone = 901_234_567_u64, user = "u-1", "registered");
+                tracing::info!(pii = true, "address: 1 Le Loi");
+            });
+        });
+
+        let entry = std::fs::read_dir(directory.path())
+            .expect("read log dir")
+            .next()
+            .expect("log file")
+            .expect("dir entry");
+        let contents = std::fs::read_to_string(entry.path()).expect("read log file");
+        let records: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        assert_eq!(records.len(), 1, "{contents}");
+        assert_eq!(records[0]["fields"]["phone"], "*******67");
+        assert_eq!(records[0]["fields"]["user"], "u-1");
+        assert_eq!(records[0]["span"]["email"], "************om");
+        assert_eq!(records[0]["spans"][0]["name"], "signup");
+    }
+
+    #[test]
+    fn secret_sanitizer_masks_span_and_event_fields() {
+        let sink = CaptureWriter::default();
+        let subscriber = Registry::default().with(
+            fmt::layer()
+                .fmt_fields(SecretSanitizer::default().field_formatter())
+                .with_ansi(false)
+                .with_writer(sink.clone()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("oa.call", access_token = "tok-123", oa_id = "oa-1");
+            span.in_scope(|| tracing::info!(Authorization = "Bearer xyz", user = "u-1", "sent"));
+        });
+
+        let output = sink.contents();
+        assert!(
+            output.contains(&format!("access_token=\"{REDACTED}\"")),
+            "{output}"
+        );
+        assert!(
+            output.contains(&format!("Authorization=\"{REDACTED}\"")),
+            "{output}"
+        );
+        assert!(output.contains("oa_id=\"oa-1\""), "{output}");
+        assert!(output.contains("user=\"u-1\""), "{output}");
+        assert!(!output.contains("tok-123") && !output.contains("xyz"));
+        assert!(glob_matches("*secret*", "webhook_secret_key"));
+        assert!(!glob_matches("authorization", "authorization_id"));
+    }
+
+    #[test]
+    fn console_layer_redacts_fields() {
+        let sink = CaptureWriter::default();
+        let redaction = PiiFilterLayer::new(Environment::Production)
+            .field_redaction()
+            .with_secrets(SecretSanitizer::default());
+        let subscriber = Registry::default().with(console_text_layer(sink.clone(), &redaction));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("oa.call", access_token = "tok-123");
+            span.in_scope(|| tracing::warn!(phone = "0901234567", "sent"));
+        });
+
+        let output = sink.contents();
+        assert!(output.starts_with(" WARN"), "{output}");
+        assert!(
+            output.contains(&format!("access_token=\"{REDACTED}\"")),
+            "{output}"
+        );
+        assert!(
+            !output.contains("tok-123") && !output.contains("0901234567"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn dispatch_sanitizes_secrets_in_json_logs() {
+        let directory = tempfile::tempdir().expect("temp dir");
+        let logging =
+            LoggingConfig::new("info", LogFormat::Json).with_file_output(directory.path());
+        let config = AppConfig::default().with_logging(logging);
+        let dispatch = build_tracing_dispatch(&config).expect("dispatcher");
+
+        tracing::dispatcher::with_default(&dispatch, || {
+            let span = tracing::info_span!("oa.call", access_token = "tok-123", oa_id = "oa-1");
+            span.in_scope(|| {
+                tracing::info!(Authorization = "Bearer xyz", phone = "0901234567", "sent");
+            });
+        });
+
+        let entry = std::fs::read_dir(directory.path())
+            .expect("read log dir")
+            .next()
+            .expect("log file")
+            .expect("dir entry");
+        let contents = std::fs::read_to_string(entry.path()).expect("read log file");
+        let record: serde_json::Value =
+            serde_json::from_str(contents.trim_end()).expect("json line");
+        assert_eq!(record["fields"]["Authorization"], REDACTED);
+        assert_eq!(record["span"]["access_token"], REDACTED);
+        assert_eq!(record["span"]["oa_id"], "oa-1");
+        // PII masking stays off outside production.
+        assert_eq!(record["fields"]["phone"], "0901234567");
+        assert!(!contents.contains("tok-123") && !contents.contains("xyz"));
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_format_emits_length_prefixed_frames() {
+        let sink = CaptureWriter::default();
+        let subscriber = Registry::default().with(
+            fmt_layer(LogFormat::MsgPack, sink.clone(), &FieldRedaction::default()).expect("layer"),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(user = "u-1", "first");
+            tracing::warn!("second");
+        });
+
+        let bytes = sink.0.lock().expect("lock poisoned").clone();
+        let mut rest = bytes.as_slice();
+        let mut records = Vec::new();
+        while !rest.is_empty() {
+            let (prefix, tail) = rest.split_at(4);
+            let len = u32::from_be_bytes(prefix.try_into().expect("prefix")) as usize;
+            let (frame, tail) = tail.split_at(len);
+            let record: serde_json::Value = rmp_serde::from_slice(frame).expect("msgpack map");
+            records.push(record);
+            rest = tail;
+        }
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["message"], "first");
+        assert_eq!(records[0]["level"], "INFO");
+        assert_eq!(records[0]["user"], "u-1");
+        assert_eq!(records[1]["message"], "second");
+        assert_eq!(records[1]["level"], "WARN");
+    }
+
+    /// Formatter replacing every event with a fixed marker line.
+    struct MarkerFormat;
+
+    impl FormatEvent<Registry, RedactingFields> for MarkerFormat {
+        fn format_event(
+            &self,
+            _ctx: &FmtContext<'_, Registry, RedactingFields>,
+            mut writer: Writer<'_>,
+            _event: &Event<'_>,
+        ) -> std::fmt::Result {
+            writeln!(writer, "CUSTOM-MARKER")
+        }
+    }
+
+    #[test]
+    fn custom_formatter_replaces_builtin_format() {
+        let directory = tempfile::tempdir().expect("temp dir");
+        let logging =
+            LoggingConfig::new("info", LogFormat::Json).with_file_output(directory.path());
+        let config = AppConfig::default().with_logging(logging);
+        let dispatch = build_tracing_dispatch_with_formatter(&config, Box::new(MarkerFormat))
+            .expect("dispatcher");
+
+        tracing::dispatcher::with_default(&dispatch, || {
+            tracing::info!("first");
+            tracing::debug!("filtered out");
+            tracing::warn!("second");
+        });
+
+        let entry = std::fs::read_dir(directory.path())
+            .expect("read log dir")
+            .next()
+            .expect("log file")
+            .expect("dir entry");
+        let contents = std::fs::read_to_string(entry.path()).expect("read log file");
+        assert_eq!(contents, "CUSTOM-MARKER\nCUSTOM-MARKER\n");
+    }
+
+    /// Formatter writing only the event fields, through the context.
+    struct FieldsOnly;
+
+    impl FormatEvent<Registry, RedactingFields> for FieldsOnly {
+        fn format_event(
+            &self,
+            ctx: &FmtContext<'_, Registry, RedactingFields>,
+            mut writer: Writer<'_>,
+            event: &Event<'_>,
+        ) -> std::fmt::Result {
+            ctx.field_format().format_fields(writer.by_ref(), event)?;
+            writeln!(writer)
+        }
+    }
+
+    #[test]
+    fn custom_formatter_fields_are_redacted() {
+        let stdout = CaptureWriter::default();
+        let config = AppConfig::default().with_environment(Environment::Production);
+        let formatter = SharedEventFormat(Arc::new(FieldsOnly));
+        let dispatch = build_dispatch_to(&config, stdout.clone(), |writer, redaction| {
+            Ok(formatter_layer(&formatter, writer, redaction))
+        })
+        .expect("dispatcher");
+
+        tracing::dispatcher::with_default(&dispatch, || {
+            tracing::info!(access_token = "tok-123", phone = "0901234567", "sent");
+        });
+
+        let output = stdout.contents();
+        assert!(output.contains(REDACTED), "{output}");
+        assert!(
+            !output.contains("tok-123") && !output.contains("0901234567"),
+            "{output}"
+        );
+    }
+
+    #[cfg(not(feature = "msgpack"))]
+    #[test]
+    fn msgpack_format_requires_msgpack_feature() {
+        let config =
+            AppConfig::default().with_logging(LoggingConfig::new("info", LogFormat::MsgPack));
+
+        assert!(matches!(
+            build_tracing_dispatch(&config),
+            Err(ObservabilityError::MsgPackUnavailable)
+        ));
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    #[test]
+    fn console_output_requires_wasm_feature() {
+        let logging = LoggingConfig::new("info", LogFormat::Text).with_output(LogOutput::Console);
+        let config = AppConfig::default().with_logging(logging);
+
+        assert!(matches!(
+            build_tracing_dispatch(&config),
+            Err(ObservabilityError::ConsoleUnavailable)
+        ));
+    }
+
     #[test]
     fn rejects_invalid_filter_expression() {
         let config =
@@ -110,8 +1695,34 @@ mod tests {
         }
     }
 
+    /// Serialises tests that install the global dispatcher.
+    static GLOBAL_DISPATCH_GUARD: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn init_from_filter_installs_without_full_config() {
+        let _guard = GLOBAL_DISPATCH_GUARD.lock().expect("lock poisoned");
+        let invalid = init_from_filter("=info", LogFormat::Text).expect_err("invalid filter");
+        assert!(matches!(
+            invalid,
+            BotError::Observability(ObservabilityError::InvalidFilter { .. })
+        ));
+
+        if tracing::dispatcher::has_been_set() {
+            return;
+        }
+        init_from_filter("info", LogFormat::Json).expect("installs subscriber");
+        assert!(tracing::dispatcher::has_been_set());
+
+        let second = init_from_filter("info", LogFormat::Json).expect_err("already installed");
+        assert!(matches!(
+            second,
+            BotError::Observability(ObservabilityError::Install { .. })
+        ));
+    }
+
     #[test]
     fn init_tracing_sets_global_dispatcher() {
+        let _guard = GLOBAL_DISPATCH_GUARD.lock().expect("lock poisoned");
         if tracing::dispatcher::has_been_set() {
             return;
         }
@@ -126,4 +1737,61 @@ mod tests {
         let app_error = AppError::from(second);
         assert!(matches!(app_error.kind, AppErrorKind::Internal));
     }
+
+    #[test]
+    fn init_tracing_or_warn_installs_when_unset() {
+        let _guard = GLOBAL_DISPATCH_GUARD.lock().expect("lock poisoned");
+        if tracing::dispatcher::has_been_set() {
+            return;
+        }
+        let config = AppConfig::default().with_logging(LoggingConfig::new("warn", LogFormat::Text));
+
+        init_tracing_or_warn(&config).expect("installs subscriber");
+        assert!(tracing::dispatcher::has_been_set());
+    }
+
+    #[test]
+    fn init_tracing_or_warn_keeps_existing_subscriber_without_building() {
+        let _guard = GLOBAL_DISPATCH_GUARD.lock().expect("lock poisoned");
+        if !tracing::dispatcher::has_been_set() {
+            let config =
+                AppConfig::default().with_logging(LoggingConfig::new("warn", LogFormat::Text));
+            let dispatch = build_tracing_dispatch(&config).expect("dispatcher");
+            // Losing the race to another test is fine: either way one is set.
+            let _ = tracing::dispatcher::set_global_default(dispatch);
+        }
+
+        let invalid =
+            AppConfig::default().with_logging(LoggingConfig::new("=info", LogFormat::Text));
+        init_tracing_or_warn(&invalid).expect("invalid filter is not checked");
+
+        let directory = tempfile::tempdir().expect("temp dir");
+        let logging =
+            LoggingConfig::new("info", LogFormat::Text).with_file_output(directory.path());
+        init_tracing_or_warn(&AppConfig::default().with_logging(logging))
+            .expect("file output is not opened");
+        let files = std::fs::read_dir(directory.path())
+            .expect("read log dir")
+            .count();
+        assert_eq!(files, 0);
+    }
+}
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]
+mod wasm_tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn console_output_installs_without_panicking() {
+        let logging = LoggingConfig::new("info", LogFormat::Text).with_output(LogOutput::Console);
+        let config = AppConfig::default().with_logging(logging);
+        let dispatch = build_tracing_dispatch(&config).expect("console dispatcher");
+
+        tracing::dispatcher::with_default(&dispatch, || {
+            tracing::info!("visible in devtools");
+            tracing::error!("reported through console.error");
+        });
+    }
 }
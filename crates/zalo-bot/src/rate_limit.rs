@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Per-source admission check consulted by
+/// [`WebhookVerifier::verify_and_rate_limit`](crate::WebhookVerifier::verify_and_rate_limit).
+///
+/// A source is whatever identifies the sender, typically the peer IP address
+/// or the OA ID. Implementations decide how requests are counted; calling
+/// [`try_acquire`](Self::try_acquire) consumes one unit of the source's
+/// budget.
+pub trait RateLimiter: Send + Sync {
+    /// Records a request from `source`, returning `false` when its budget is
+    /// exhausted.
+    fn try_acquire(&self, source: &str) -> bool;
+}
+
+/// In-memory [`RateLimiter`] admitting at most `limit` requests per source in
+/// each fixed `window`.
+///
+/// Windows start with the first request from a source. Expired windows are
+/// pruned whenever a new source is seen, so memory stays proportional to the
+/// number of sources active within one window.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use zalo_bot::rate_limit::{FixedWindowRateLimiter, RateLimiter};
+///
+/// let limiter = FixedWindowRateLimiter::new(1, Duration::from_secs(60));
+/// assert!(limiter.try_acquire("10.0.0.1"));
+/// assert!(!limiter.try_acquire("10.0.0.1"));
+/// assert!(limiter.try_acquire("10.0.0.2"));
+/// ```
+#[derive(Debug)]
+pub struct FixedWindowRateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl FixedWindowRateLimiter {
+    /// Creates a limiter admitting `limit` requests per source and `window`.
+    #[must_use]
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the number of requests admitted per source and window.
+    #[must_use]
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// Returns the length of each window.
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+impl RateLimiter for FixedWindowRateLimiter {
+    fn try_acquire(&self, source: &str) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((started, count)) = windows.get_mut(source) {
+            if now.duration_since(*started) < self.window {
+                if *count >= self.limit {
+                    return false;
+                }
+                *count += 1;
+            } else {
+                *started = now;
+                *count = 1;
+            }
+            return self.limit > 0;
+        }
+
+        windows.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+        windows.insert(source.to_owned(), (now, 1));
+        self.limit > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restarts_window_after_expiry() {
+        let limiter = FixedWindowRateLimiter::new(1, Duration::ZERO);
+
+        assert!(limiter.try_acquire("oa-1"));
+        assert!(limiter.try_acquire("oa-1"));
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use sha2::{Digest, Sha256};
+
+use crate::webhook::VerificationOutcome;
+
+/// Digest identifying a `(payload, signature)` pair.
+pub(crate) type CacheKey = [u8; 32];
+
+/// Bounded, thread-safe LRU of webhook verification outcomes.
+///
+/// Entries are keyed by a SHA-256 digest of the payload and signature, so the
+/// cache never retains request bodies. The capacity is expected to be small,
+/// which keeps the linear recency bookkeeping cheap.
+#[derive(Debug)]
+pub(crate) struct VerificationCache {
+    capacity: usize,
+    entries: Mutex<VecDeque<(CacheKey, VerificationOutcome)>>,
+    hits: AtomicU64,
+}
+
+impl VerificationCache {
+    /// Creates an empty cache holding at most `capacity` results.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the maximum number of cached results.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Derives the cache key for the signed parts and their signature header.
+    pub(crate) fn key(parts: &[&[u8]], signature: &str) -> CacheKey {
+        let mut hasher = Sha256::new();
+        // Count and length prefixes keep `(ab, c)`, `(a, bc)` and `(abc)`
+        // distinct.
+        hasher.update((parts.len() as u64).to_be_bytes());
+        for part in parts {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part);
+        }
+        hasher.update(signature.as_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Returns the cached result and marks it as most recently used.
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<VerificationOutcome> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let position = entries.iter().position(|(cached, _)| cached == key)?;
+        let entry = entries.remove(position)?;
+        entries.push_back(entry);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+
+        Some(entry.1)
+    }
+
+    /// Stores a result, evicting the least recently used entry when full.
+    pub(crate) fn insert(&self, key: CacheKey, outcome: VerificationOutcome) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(position) = entries.iter().position(|(cached, _)| *cached == key) {
+            entries.remove(position);
+        } else if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back((key, outcome));
+    }
+
+    /// Returns how many lookups were answered from the cache.
+    #[cfg(test)]
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used_entry() {
+        let cache = VerificationCache::new(2);
+        let first = VerificationCache::key(&[b"a"], "1");
+        let second = VerificationCache::key(&[b"b"], "2");
+        let third = VerificationCache::key(&[b"c"], "3");
+
+        cache.insert(first, VerificationOutcome::Valid);
+        cache.insert(second, VerificationOutcome::Mismatch);
+        assert_eq!(cache.get(&first), Some(VerificationOutcome::Valid));
+        cache.insert(third, VerificationOutcome::Malformed);
+
+        assert_eq!(cache.get(&second), None);
+        assert_eq!(cache.get(&first), Some(VerificationOutcome::Valid));
+        assert_eq!(cache.get(&third), Some(VerificationOutcome::Malformed));
+    }
+}
@@ -1,69 +1,1111 @@
-use hmac::{Hmac, Mac};
-use sha2::Sha256;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::error::{BotResult, SignatureError};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use hkdf::Hkdf;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use zalo_signing::{InvalidLength, SignatureAlgorithm, SIGNATURE_LEN};
+use zalo_types::{WebhookConfig, REDACTED};
 
-type HmacSha256 = Hmac<Sha256>;
+use crate::error::{BotError, BotResult, SignatureError};
+use crate::multipart::MultipartParts;
+use crate::rate_limit::RateLimiter;
+use crate::verification_cache::VerificationCache;
+
+/// Number of digest bytes (hex-encoded to twice as many characters) exposed by
+/// [`WebhookVerifier::secret_fingerprint`].
+const FINGERPRINT_LEN: usize = 4;
+
+/// Byte placed between the prefix and the body by
+/// [`WebhookVerifier::verify_composite`].
+pub const COMPOSITE_SEPARATOR: u8 = b'.';
+
+/// Largest `skew_buckets` accepted by [`WebhookVerifier::verify_time_bucketed`].
+///
+/// Every accepted bucket costs one key derivation per secret, so the window
+/// is kept small.
+pub const MAX_SKEW_BUCKETS: u64 = 4;
+
+/// Length in bytes of keys derived by [`WebhookVerifier::from_master_key`].
+const DERIVED_KEY_LEN: usize = 32;
+
+/// Storage for secret key material, overwritten on drop with the `zeroize`
+/// feature.
+///
+/// Key bytes are moved in rather than copied, so no unwiped duplicate is
+/// left behind; `Debug` prints [`REDACTED`] instead of the bytes.
+#[derive(Clone, Eq, PartialEq)]
+struct SecretBytes(Vec<u8>);
+
+impl std::fmt::Debug for SecretBytes {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_tuple("SecretBytes")
+            .field(&REDACTED)
+            .finish()
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl std::ops::Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+/// Result of [`WebhookVerifier::verify_detailed`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationOutcome {
+    /// The signature matches the payload.
+    Valid,
+    /// No signature header was provided.
+    Missing,
+    /// The signature is not hex or its length maps to no accepted algorithm.
+    Malformed,
+    /// The signature is well-formed but does not match the payload.
+    Mismatch,
+}
+
+impl VerificationOutcome {
+    /// Returns `true` for [`VerificationOutcome::Valid`].
+    #[must_use]
+    pub fn is_valid(self) -> bool {
+        self == Self::Valid
+    }
+
+    /// Returns a stable label suitable for audit logs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Missing => "missing",
+            Self::Malformed => "malformed",
+            Self::Mismatch => "mismatch",
+        }
+    }
+}
 
 /// Verifies webhook signatures sent by the Zalo platform.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// A verifier holds one or more secrets. The first (primary) secret signs
+/// payloads, while verification accepts a signature produced by any of them,
+/// which allows secrets to be rotated without dropping in-flight webhooks.
+///
+/// Only HMAC-SHA256 signatures are accepted by default; see
+/// [`with_algorithm`](Self::with_algorithm) and
+/// [`with_algorithms`](Self::with_algorithms) to use other hash functions.
+///
+/// Clones share the optional verification cache, while equality only
+/// compares secrets and algorithms. With the `zeroize` feature the stored
+/// secrets are overwritten when the verifier is dropped.
+#[derive(Clone, Debug)]
 pub struct WebhookVerifier {
-    secret: Vec<u8>,
+    secrets: Vec<SecretBytes>,
+    algorithms: Vec<SignatureAlgorithm>,
+    cache: Option<Arc<VerificationCache>>,
 }
 
+impl PartialEq for WebhookVerifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.secrets == other.secrets && self.algorithms == other.algorithms
+    }
+}
+
+impl Eq for WebhookVerifier {}
+
 impl WebhookVerifier {
     /// Creates a new verifier using the provided shared secret.
     ///
     /// # Errors
     ///
-    /// Returns [`SignatureError::InvalidSecretLength`] when the secret is empty.
+    /// Returns [`SignatureError::InvalidSecretLength`] when the secret is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("top-secret")?;
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(secret: impl AsRef<[u8]>) -> Result<Self, SignatureError> {
+        Self::from_secret(SecretBytes::from(secret.as_ref().to_vec()))
+    }
+
+    /// Creates a verifier taking ownership of already-stored key material.
+    fn from_secret(secret: SecretBytes) -> Result<Self, SignatureError> {
+        // Ensure the secret satisfies the requirements of the underlying HMAC
+        // implementation.
+        zalo_signing::check_key(&secret)?;
+
+        Ok(Self {
+            secrets: vec![secret],
+            algorithms: vec![SignatureAlgorithm::default()],
+            cache: None,
+        })
+    }
+
+    /// Creates a verifier signing and verifying with `algorithm` only.
+    ///
+    /// [`new`](Self::new) is the HMAC-SHA256 equivalent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::InvalidSecretLength`] when the secret is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::{SignatureAlgorithm, WebhookVerifier};
+    ///
+    /// let verifier = WebhookVerifier::with_algorithm("secret", SignatureAlgorithm::Sha512)?;
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// assert_eq!(signature.len(), 128);
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_algorithm(
+        secret: impl AsRef<[u8]>,
+        algorithm: SignatureAlgorithm,
+    ) -> Result<Self, SignatureError> {
+        Ok(Self::new(secret)?.with_algorithms([algorithm]))
+    }
+
+    /// Creates a verifier from a hex-encoded secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::InvalidSecretLength`] when the input is not
+    /// valid hex or decodes to a rejected key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new_from_hex("736563726574")?;
+    /// assert_eq!(verifier, WebhookVerifier::new("secret")?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_from_hex(secret: &str) -> Result<Self, SignatureError> {
+        let bytes = zalo_signing::decode_hex(secret.trim())
+            .ok_or(SignatureError::InvalidSecretLength(InvalidLength))?;
+
+        Self::from_secret(SecretBytes::from(bytes))
+    }
+
+    /// Creates a verifier from a standard base64-encoded secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::InvalidSecretLength`] when the input is not
+    /// valid base64 or decodes to a rejected key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new_from_base64("c2VjcmV0")?;
+    /// assert_eq!(verifier, WebhookVerifier::new("secret")?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_from_base64(secret: &str) -> Result<Self, SignatureError> {
+        let bytes = BASE64
+            .decode(secret.trim())
+            .map_err(|_| SignatureError::InvalidSecretLength(InvalidLength))?;
+
+        Self::from_secret(SecretBytes::from(bytes))
+    }
+
+    /// Creates a verifier accepting any of the provided secrets.
+    ///
+    /// The first secret is the primary one used by
+    /// [`sign_payload`](Self::sign_payload).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::MissingSecret`] when the list is empty and
+    /// [`SignatureError::InvalidSecretLength`] when a secret is rejected by the
+    /// HMAC implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let previous = WebhookVerifier::new("previous")?;
+    /// let verifier = WebhookVerifier::with_secrets(["current", "previous"])?;
+    /// let signature = previous.sign_payload(b"payload")?;
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_secrets<I, S>(secrets: I) -> Result<Self, SignatureError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let mut collected = Vec::new();
+        for secret in secrets {
+            let secret_bytes = secret.as_ref();
+            zalo_signing::check_key(secret_bytes)?;
+            collected.push(SecretBytes::from(secret_bytes.to_vec()));
+        }
+        if collected.is_empty() {
+            return Err(SignatureError::MissingSecret);
+        }
+
+        Ok(Self {
+            secrets: collected,
+            algorithms: vec![SignatureAlgorithm::default()],
+            cache: None,
+        })
+    }
+
+    /// Creates a verifier from the webhook configuration block.
+    ///
+    /// A `secret_base64` value is decoded as with
+    /// [`new_from_base64`](Self::new_from_base64).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BotError::Types`] when the block fails validation and
+    /// [`BotError::Signature`] when no secret is configured or a secret is
+    /// rejected by the HMAC implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    /// use zalo_types::WebhookConfig;
+    ///
+    /// let config = WebhookConfig::default().with_secrets(["current", "previous"]);
+    /// let verifier = WebhookVerifier::from_config(&config)?;
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_config(config: &WebhookConfig) -> BotResult<Self> {
+        config
+            .validate()
+            .map_err(|error| BotError::Types(error.into()))?;
+        let verifier = match config.secret_base64() {
+            Some(encoded) => Self::new_from_base64(encoded)?,
+            None => Self::with_secrets(config.resolved_secrets())?,
+        };
+
+        Ok(verifier)
+    }
+
+    /// Creates a verifier whose HMAC key is derived from a master secret via
+    /// HKDF-SHA256.
+    ///
+    /// The `info` parameter scopes the derived key to a purpose, so a single
+    /// master secret can back several independent verifiers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::InvalidSecretLength`] when the key cannot be
+    /// derived or rejected by the HMAC implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::from_master_key("master", b"webhook", b"salt")?;
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_master_key(
+        master: impl AsRef<[u8]>,
+        info: &[u8],
+        salt: &[u8],
+    ) -> Result<Self, SignatureError> {
+        let hkdf = Hkdf::<Sha256>::new(Some(salt), master.as_ref());
+        // Derive straight into the stored buffer instead of a stack array
+        // that would outlive the verifier unwiped.
+        let mut key = SecretBytes::from(vec![0_u8; DERIVED_KEY_LEN]);
+        hkdf.expand(info, &mut key.0)
+            .map_err(|_| SignatureError::InvalidSecretLength(InvalidLength))?;
+
+        Self::from_secret(key)
+    }
+
+    /// Replaces the set of algorithms accepted by [`verify`](Self::verify).
+    ///
+    /// Signature headers carry no algorithm prefix, so the algorithm is
+    /// inferred from the decoded signature length (20 bytes for SHA-1, 32 for
+    /// SHA-256, 64 for SHA-512). Signatures whose length maps to no accepted
+    /// algorithm are rejected, as is every signature when the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::{SignatureAlgorithm, WebhookVerifier};
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?
+    ///     .with_algorithms([SignatureAlgorithm::Sha1, SignatureAlgorithm::Sha256]);
+    /// let signature = zalo_signing::sign_with(SignatureAlgorithm::Sha1, b"secret", b"payload")?;
+    /// verifier.verify(b"payload", Some(&zalo_signing::encode_hex(signature)))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_algorithms(
+        mut self,
+        algorithms: impl IntoIterator<Item = SignatureAlgorithm>,
+    ) -> Self {
+        self.algorithms.clear();
+        for algorithm in algorithms {
+            if !self.algorithms.contains(&algorithm) {
+                self.algorithms.push(algorithm);
+            }
+        }
+        // Cached results were computed for the previous algorithm set.
+        if let Some(cache) = &self.cache {
+            self.cache = Some(Arc::new(VerificationCache::new(cache.capacity())));
+        }
+        self
+    }
+
+    /// Enables a bounded cache of verification results.
+    ///
+    /// Zalo retries deliver byte-identical payloads; with the cache enabled a
+    /// repeated `(payload, signature)` pair is answered from the previous
+    /// result instead of recomputing every HMAC. At most `capacity` results
+    /// are kept, evicting the least recently used one. Only a SHA-256 digest
+    /// of each pair is stored. A capacity of zero disables the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?.with_verification_cache(256);
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// verifier.verify(b"payload", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_verification_cache(mut self, capacity: usize) -> Self {
+        self.cache = (capacity > 0).then(|| Arc::new(VerificationCache::new(capacity)));
+        self
+    }
+
+    /// Returns the algorithms accepted by [`verify`](Self::verify).
+    #[must_use]
+    pub fn algorithms(&self) -> &[SignatureAlgorithm] {
+        &self.algorithms
+    }
+
+    /// Computes the expected signature for a payload using the primary secret
+    /// and the first accepted algorithm, or HMAC-SHA256 when none is.
+    pub fn sign_payload(&self, payload: &[u8]) -> Result<String, SignatureError> {
+        let signature =
+            zalo_signing::sign_with(self.primary_algorithm(), self.primary_secret(), payload)?;
+        Ok(zalo_signing::encode_hex(signature))
+    }
+
+    /// Validates the provided signature against the payload.
+    ///
+    /// The signature is accepted when it matches any configured secret under
+    /// the accepted algorithm matching its length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::Missing`] when the signature header is absent
+    /// and [`SignatureError::VerificationFailed`] when the signature does not
+    /// match the payload or its length maps to no accepted algorithm.
+    ///
+    /// Malformed hex signatures follow the same path as well-formed but wrong
+    /// ones: the MAC is always computed and compared in constant time against
+    /// a placeholder of the expected length, so the response time does not
+    /// reveal whether the header decoded successfully.
+    pub fn verify(&self, payload: &[u8], signature: Option<&str>) -> BotResult<()> {
+        outcome_to_result(self.verify_detailed(payload, signature))
+    }
+
+    /// Validates a payload against a raw signature header such as
+    /// `sha256=<hex>`.
+    ///
+    /// An optional `algorithm=` prefix (`sha1`, `sha256` or `sha512`, case
+    /// insensitive, with optional whitespace around `=`) must name an
+    /// accepted algorithm, and the signature is then only checked under that
+    /// algorithm. A header without a prefix is treated as bare hex, as in
+    /// [`verify`](Self::verify).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::AlgorithmMismatch`] when the prefix names an
+    /// unknown or unaccepted algorithm, and otherwise the same errors as
+    /// [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let header = format!("sha256={}", verifier.sign_payload(b"payload")?);
+    /// verifier.verify_header(b"payload", Some(&header))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_header(&self, payload: &[u8], header: Option<&str>) -> BotResult<()> {
+        let Some(header) = header else {
+            return Err(SignatureError::Missing.into());
+        };
+        let Some((name, signature)) = header.split_once('=') else {
+            return self.verify(payload, Some(header));
+        };
+
+        let name = name.trim();
+        let algorithm = SignatureAlgorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.as_str().eq_ignore_ascii_case(name))
+            .filter(|algorithm| self.algorithms.contains(algorithm))
+            .ok_or_else(|| SignatureError::AlgorithmMismatch(name.to_owned()))?;
+        let signature = signature.trim();
+        // Keep a signature of another accepted algorithm from matching under
+        // the prefix's name.
+        if signature.len() != 2 * algorithm.signature_len() {
+            return Err(SignatureError::VerificationFailed.into());
+        }
+
+        self.verify(payload, Some(signature))
+    }
+
+    /// Validates a payload against several candidate signatures, succeeding
+    /// when any of them matches.
+    ///
+    /// This serves relays that forward more than one signature, for example
+    /// while they rotate their own keys. Every candidate is checked as in
+    /// [`verify`](Self::verify), without stopping at the first match, so the
+    /// position of the valid signature is not observable by timing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::Missing`] when `signatures` is empty and
+    /// [`SignatureError::VerificationFailed`] when no candidate matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// let header = format!("deadbeef,{signature}");
+    /// let candidates: Vec<&str> = header.split(',').collect();
+    /// verifier.verify_any(b"payload", &candidates)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_any(&self, payload: &[u8], signatures: &[&str]) -> BotResult<()> {
+        if signatures.is_empty() {
+            return Err(SignatureError::Missing.into());
+        }
+
+        let mut valid = false;
+        for signature in signatures {
+            valid |= self
+                .check_signature(&[payload], signature.trim())
+                .is_valid();
+        }
+        if !valid {
+            return Err(SignatureError::VerificationFailed.into());
+        }
+
+        Ok(())
+    }
+
+    /// Validates a payload as in [`verify`](Self::verify), then charges the
+    /// delivery to `source` on `limiter`.
+    ///
+    /// This bounds valid but abusive traffic, for example from a leaked
+    /// secret, per sender. `source` identifies the sender, such as the peer
+    /// IP address or the OA ID. Deliveries failing verification are rejected
+    /// first and do not consume the source's budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BotError::RateLimited`] when `limiter` rejects the source,
+    /// and otherwise the same errors as [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zalo_bot::{FixedWindowRateLimiter, WebhookVerifier};
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let limiter = FixedWindowRateLimiter::new(100, Duration::from_secs(60));
+    /// let signature = verifier.sign_payload(b"payload")?;
+    /// verifier.verify_and_rate_limit(b"payload", Some(&signature), "203.0.113.7", &limiter)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_and_rate_limit(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+        source: &str,
+        limiter: &dyn RateLimiter,
+    ) -> BotResult<()> {
+        self.verify(payload, signature)?;
+        if !limiter.try_acquire(source) {
+            return Err(BotError::RateLimited(source.to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// Validates a signature computed over `prefix`, a `.` separator
+    /// ([`COMPOSITE_SEPARATOR`]) and `body`.
+    ///
+    /// This covers schemes that sign `timestamp.body` or `app_id.body`; the
+    /// parts are fed to the MAC in order, so no combined buffer is allocated.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let signature = verifier.sign_payload(b"1700000000.{}")?;
+    /// verifier.verify_composite(b"1700000000", b"{}", Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_composite(
+        &self,
+        prefix: &[u8],
+        body: &[u8],
+        signature: Option<&str>,
+    ) -> BotResult<()> {
+        outcome_to_result(self.verify_parts(&[prefix, &[COMPOSITE_SEPARATOR], body], signature))
+    }
+
+    /// Validates a signature over `timestamp.body`, then checks the signed
+    /// Unix `timestamp` (in seconds) against `now`.
+    ///
+    /// Timestamps older than `max_age` and timestamps more than
+    /// `max_future_skew` ahead of `now` are rejected with distinct errors, so
+    /// a stale delivery can be told apart from a sender whose clock is wrong
+    /// or a forged timestamp. The timestamp is only checked once the
+    /// signature is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::Expired`] or
+    /// [`SignatureError::TimestampInFuture`] when the timestamp is out of
+    /// range, and otherwise the same errors as [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let signature = verifier.sign_payload(b"1700000000.{}")?;
+    /// let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_030);
+    /// verifier.verify_with_clock_skew(
+    ///     b"{}",
+    ///     Some(&signature),
+    ///     1_700_000_000,
+    ///     now,
+    ///     Duration::from_secs(300),
+    ///     Duration::from_secs(30),
+    /// )?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_with_clock_skew(
+        &self,
+        body: &[u8],
+        signature: Option<&str>,
+        timestamp: u64,
+        now: SystemTime,
+        max_age: Duration,
+        max_future_skew: Duration,
+    ) -> BotResult<()> {
+        self.verify_composite(timestamp.to_string().as_bytes(), body, signature)?;
+        check_timestamp(timestamp, now, max_age, max_future_skew)?;
+
+        Ok(())
+    }
+
+    /// Validates a signature over `timestamp.payload` and rejects replays whose
+    /// signed Unix `timestamp` (in seconds) is more than `tolerance` away
+    /// from `now`.
+    ///
+    /// Binding the timestamp into the MAC stops a captured request from being
+    /// replayed later, which [`verify`](Self::verify) alone cannot detect.
+    /// Timestamps slightly ahead of `now` are accepted within the same
+    /// `tolerance`, absorbing clock skew between Zalo and this host. This is
+    /// [`verify_with_clock_skew`](Self::verify_with_clock_skew) with one bound
+    /// for both directions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::Expired`] when `now - timestamp` exceeds
+    /// `tolerance`, [`SignatureError::TimestampInFuture`] when
+    /// `timestamp - now` does, and otherwise the same errors as
+    /// [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let signature = verifier.sign_payload(b"1700000000.{}")?;
+    /// let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_060);
+    /// verifier.verify_with_timestamp(b"{}", Some(&signature), 1_700_000_000, now, Duration::from_secs(300))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_with_timestamp(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+        timestamp: u64,
+        now: SystemTime,
+        tolerance: Duration,
+    ) -> BotResult<()> {
+        self.verify_with_clock_skew(payload, signature, timestamp, now, tolerance, tolerance)
+    }
+
+    /// Computes the signature for a payload under the time-bucketed key of the
+    /// bucket containing `now`.
+    ///
+    /// The signature uses the first accepted algorithm, like
+    /// [`sign_payload`](Self::sign_payload). See
+    /// [`verify_time_bucketed`](Self::verify_time_bucketed) for how the key is
+    /// derived.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::InvalidSecretLength`] when the HMAC
+    /// implementation rejects the derived key.
+    pub fn sign_time_bucketed(
+        &self,
+        payload: &[u8],
+        now: SystemTime,
+        bucket_secs: u64,
+    ) -> Result<String, SignatureError> {
+        let bucket = time_bucket(now, bucket_secs);
+        let algorithm = self.primary_algorithm();
+        let key = bucket_key(algorithm, self.primary_secret(), bucket)?;
+        let signature = zalo_signing::sign_with(algorithm, &key, payload)?;
+        Ok(zalo_signing::encode_hex(signature))
+    }
+
+    /// Validates a signature made with a key that rotates every
+    /// `bucket_secs` seconds.
+    ///
+    /// The key of bucket `n` (seconds since the Unix epoch divided by
+    /// `bucket_secs`) is the HMAC of the big-endian `n` under each configured
+    /// secret, using the first accepted algorithm. The signature is accepted
+    /// for the bucket containing `now` and for up to `skew_buckets` buckets on
+    /// either side, which absorbs clock drift between sender and receiver.
+    /// Every candidate key is checked, so the matching bucket is not
+    /// observable by timing. A `bucket_secs` of zero is treated as one second.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::SkewTooLarge`] when `skew_buckets` exceeds
+    /// [`MAX_SKEW_BUCKETS`], and otherwise the same errors as
+    /// [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("master")?;
+    /// let sent = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    /// let signature = verifier.sign_time_bucketed(b"payload", sent, 30)?;
+    /// let received = sent + Duration::from_secs(35);
+    /// verifier.verify_time_bucketed(b"payload", Some(&signature), received, 30, 1)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_time_bucketed(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+        now: SystemTime,
+        bucket_secs: u64,
+        skew_buckets: u64,
+    ) -> BotResult<()> {
+        if skew_buckets > MAX_SKEW_BUCKETS {
+            return Err(SignatureError::SkewTooLarge(skew_buckets).into());
+        }
+        let Some(signature) = signature else {
+            return Err(SignatureError::Missing.into());
+        };
+
+        let algorithm = self.primary_algorithm();
+        let current = time_bucket(now, bucket_secs);
+        let first = current.saturating_sub(skew_buckets);
+        let last = current.saturating_add(skew_buckets);
+        let mut secrets = Vec::new();
+        for secret in &self.secrets {
+            for bucket in first..=last {
+                secrets.push(SecretBytes::from(bucket_key(algorithm, secret, bucket)?));
+            }
+        }
+        let bucketed = Self {
+            secrets,
+            algorithms: self.algorithms.clone(),
+            cache: None,
+        };
+
+        outcome_to_result(bucketed.check_signature(&[payload], signature))
+    }
+
+    /// Validates a signature computed over selected fields of a JSON payload.
+    ///
+    /// The values of `field_order` are taken from the top level of `value`
+    /// and joined with [`COMPOSITE_SEPARATOR`] in the given order. String
+    /// values contribute their contents; any other value contributes its
+    /// compact JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SignatureError::MissingField`] when a listed field is absent
+    /// (or `value` is not an object), checked before the signature itself.
+    /// Otherwise returns the same errors as [`verify`](Self::verify).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let body = json!({ "event": "follow", "timestamp": 1700000000, "extra": true });
+    /// let signature = verifier.sign_payload(b"follow.1700000000")?;
+    /// verifier.verify_fields(&body, &["event", "timestamp"], Some(&signature))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_fields(
+        &self,
+        value: &serde_json::Value,
+        field_order: &[&str],
+        signature: Option<&str>,
+    ) -> BotResult<()> {
+        let values = field_order
+            .iter()
+            .map(|&field| match value.get(field) {
+                Some(serde_json::Value::String(text)) => Ok(text.clone()),
+                Some(other) => Ok(other.to_string()),
+                None => Err(SignatureError::MissingField(field.to_owned())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut parts: Vec<&[u8]> = Vec::with_capacity(values.len() * 2);
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                parts.push(&[COMPOSITE_SEPARATOR]);
+            }
+            parts.push(value.as_bytes());
+        }
+
+        outcome_to_result(self.verify_parts(&parts, signature))
+    }
+
+    /// Verifies the payload and parses it into an event, keeping the exact
+    /// bytes that were verified.
+    ///
+    /// The signature is checked before any parsing happens.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`verify`](Self::verify), and
+    /// [`BotError::Payload`] when the verified bytes do not deserialise
+    /// into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let payload = br#"{"event_name":"follow"}"#;
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let signature = verifier.sign_payload(payload)?;
+    /// let envelope = verifier.verify_and_envelope::<serde_json::Value>(payload, Some(&signature))?;
+    /// assert_eq!(envelope.event()["event_name"], "follow");
+    /// assert_eq!(envelope.raw(), payload);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_and_envelope<T: DeserializeOwned>(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+    ) -> BotResult<WebhookEventEnvelope<T>> {
+        self.verify(payload, signature)?;
+        let verified_at = Instant::now();
+        let event = serde_json::from_slice(payload).map_err(BotError::Payload)?;
+
+        Ok(WebhookEventEnvelope {
+            event,
+            raw: payload.to_vec(),
+            verified_at,
+        })
+    }
+
+    /// Validates the provided signature and reports why it was rejected.
+    ///
+    /// This is the structured counterpart of [`verify`](Self::verify) for
+    /// audit logging. Both paths do the same constant-time work, so a
+    /// [`VerificationOutcome::Malformed`] result is not observable by timing.
     ///
     /// # Examples
     ///
     /// ```
-    /// use zalo_bot::webhook::WebhookVerifier;
+    /// use zalo_bot::webhook::{VerificationOutcome, WebhookVerifier};
     ///
-    /// let verifier = WebhookVerifier::new("top-secret")?;
+    /// let verifier = WebhookVerifier::new("secret")?;
     /// let signature = verifier.sign_payload(b"payload")?;
-    /// verifier.verify(b"payload", Some(&signature))?;
+    /// assert_eq!(verifier.verify_detailed(b"payload", Some(&signature)), VerificationOutcome::Valid);
+    /// assert_eq!(verifier.verify_detailed(b"payload", Some("zz")), VerificationOutcome::Malformed);
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// ```
-    pub fn new(secret: impl AsRef<[u8]>) -> Result<Self, SignatureError> {
-        let secret_bytes = secret.as_ref();
-        // Ensure the secret satisfies the requirements of the underlying HMAC
-        // implementation.
-        HmacSha256::new_from_slice(secret_bytes)?;
+    #[must_use]
+    pub fn verify_detailed(&self, payload: &[u8], signature: Option<&str>) -> VerificationOutcome {
+        self.verify_parts(&[payload], signature)
+    }
 
-        Ok(Self {
-            secret: secret_bytes.to_vec(),
-        })
+    /// Verifies a signature over the concatenation of `parts`, consulting the
+    /// cache when enabled.
+    fn verify_parts(&self, parts: &[&[u8]], signature: Option<&str>) -> VerificationOutcome {
+        let Some(signature) = signature else {
+            return VerificationOutcome::Missing;
+        };
+
+        match &self.cache {
+            Some(cache) => {
+                let key = VerificationCache::key(parts, signature);
+                cache.get(&key).unwrap_or_else(|| {
+                    let outcome = self.check_signature(parts, signature);
+                    cache.insert(key, outcome);
+                    outcome
+                })
+            }
+            None => self.check_signature(parts, signature),
+        }
     }
 
-    /// Computes the expected signature for a payload.
-    pub fn sign_payload(&self, payload: &[u8]) -> Result<String, SignatureError> {
-        let mut mac = HmacSha256::new_from_slice(&self.secret)?;
-        mac.update(payload);
-        let result = mac.finalize().into_bytes();
-        Ok(hex::encode(result))
+    /// Checks the signature against every secret.
+    ///
+    /// Malformed hex signatures follow the same path as well-formed but wrong
+    /// ones (see [`verify`](Self::verify)).
+    fn check_signature(&self, parts: &[&[u8]], signature: &str) -> VerificationOutcome {
+        let decoded = zalo_signing::decode_hex(signature);
+        let detected = decoded
+            .as_deref()
+            .and_then(|bytes| self.algorithm_for_len(bytes.len()));
+        let (algorithm, signature_bytes, well_formed) = match (detected, decoded) {
+            (Some(algorithm), Some(bytes)) => (algorithm, bytes, true),
+            _ => (
+                SignatureAlgorithm::default(),
+                vec![0_u8; SIGNATURE_LEN],
+                false,
+            ),
+        };
+        // Every secret is checked without short-circuiting so the position of
+        // the matching secret does not influence timing. Keys were validated
+        // by the constructors, so a key error cannot occur here.
+        let mut matches = false;
+        for secret in &self.secrets {
+            matches |= zalo_signing::verify_parts(algorithm, secret, parts, &signature_bytes)
+                .unwrap_or(false);
+        }
+
+        match (well_formed, matches) {
+            (false, _) => VerificationOutcome::Malformed,
+            (true, true) => VerificationOutcome::Valid,
+            (true, false) => VerificationOutcome::Mismatch,
+        }
     }
 
-    /// Validates the provided signature against the payload.
+    /// Verifies a `multipart/form-data` body and returns an iterator over its
+    /// parts.
+    ///
+    /// The signature is checked against the untouched body bytes before any
+    /// parsing happens; the returned parts borrow from `body`, so handlers do
+    /// not parse or copy the upload twice.
     ///
     /// # Errors
     ///
-    /// Returns [`SignatureError::Missing`] when the signature header is absent
-    /// and [`SignatureError::VerificationFailed`] when the signature does not
-    /// match the payload.
-    pub fn verify(&self, payload: &[u8], signature: Option<&str>) -> BotResult<()> {
-        let signature = signature.ok_or(SignatureError::Missing)?;
-        let signature_bytes =
-            hex::decode(signature).map_err(|_| SignatureError::VerificationFailed)?;
-        let mut mac = HmacSha256::new_from_slice(&self.secret).map_err(SignatureError::from)?;
-        mac.update(payload);
-        mac.verify_slice(&signature_bytes)
-            .map_err(|_| SignatureError::VerificationFailed)?;
+    /// Returns the same errors as [`verify`](Self::verify). Structural
+    /// problems in the body are reported by the iterator as
+    /// [`MultipartError`](crate::error::MultipartError) items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let body = b"--b\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n--b--\r\n";
+    /// let verifier = WebhookVerifier::new("secret")?;
+    /// let signature = verifier.sign_payload(body)?;
+    /// let mut parts = verifier.verify_multipart(body, "b", Some(&signature))?;
+    /// assert_eq!(parts.next().unwrap()?.body(), b"1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_multipart<'a>(
+        &self,
+        body: &'a [u8],
+        boundary: &str,
+        signature: Option<&str>,
+    ) -> BotResult<MultipartParts<'a>> {
+        self.verify(body, signature)?;
 
-        Ok(())
+        Ok(MultipartParts::new(body, boundary))
+    }
+
+    /// Returns a short, non-reversible fingerprint of the primary secret.
+    ///
+    /// The fingerprint is the first eight hex characters of the SHA-256 digest
+    /// of the secret. It is safe to log and lets operators confirm that two
+    /// deployments share a secret without revealing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_bot::webhook::WebhookVerifier;
+    ///
+    /// let fingerprint = WebhookVerifier::new("top-secret")?.secret_fingerprint();
+    /// assert_eq!(fingerprint.len(), 8);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn secret_fingerprint(&self) -> String {
+        let digest = Sha256::digest(self.primary_secret());
+        zalo_signing::encode_hex(&digest[..FINGERPRINT_LEN])
+    }
+
+    fn algorithm_for_len(&self, len: usize) -> Option<SignatureAlgorithm> {
+        SignatureAlgorithm::from_signature_len(len)
+            .filter(|algorithm| self.algorithms.contains(algorithm))
+    }
+
+    /// Returns the algorithm used for signing: the first accepted one, or
+    /// HMAC-SHA256 when none is.
+    fn primary_algorithm(&self) -> SignatureAlgorithm {
+        self.algorithms.first().copied().unwrap_or_default()
+    }
+
+    fn primary_secret(&self) -> &[u8] {
+        // Constructors guarantee at least one secret.
+        &self.secrets[0]
+    }
+}
+
+/// Verified webhook event together with the bytes it was parsed from.
+///
+/// Produced by [`WebhookVerifier::verify_and_envelope`]. The raw bytes are
+/// exactly those whose signature was checked, so handlers can archive or
+/// re-sign them without re-serialising the event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookEventEnvelope<T> {
+    event: T,
+    raw: Vec<u8>,
+    verified_at: Instant,
+}
+
+impl<T> WebhookEventEnvelope<T> {
+    /// Returns the parsed event.
+    #[must_use]
+    pub fn event(&self) -> &T {
+        &self.event
+    }
+
+    /// Returns the verified payload bytes.
+    #[must_use]
+    pub fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Returns when the signature was verified.
+    #[must_use]
+    pub fn verified_at(&self) -> Instant {
+        self.verified_at
+    }
+
+    /// Splits the envelope into the event and the raw bytes.
+    #[must_use]
+    pub fn into_parts(self) -> (T, Vec<u8>) {
+        (self.event, self.raw)
+    }
+}
+
+/// Returns the index of the `bucket_secs`-long window containing `now`.
+fn time_bucket(now: SystemTime, bucket_secs: u64) -> u64 {
+    let elapsed = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+    elapsed.as_secs() / bucket_secs.max(1)
+}
+
+/// Derives the HMAC key used for `bucket` from a configured secret.
+fn bucket_key(
+    algorithm: SignatureAlgorithm,
+    secret: &[u8],
+    bucket: u64,
+) -> Result<Vec<u8>, SignatureError> {
+    let key = zalo_signing::sign_with(algorithm, secret, &bucket.to_be_bytes())?;
+    Ok(key)
+}
+
+/// Checks that the Unix `timestamp` is at most `max_age` behind and
+/// `max_future_skew` ahead of `now`.
+fn check_timestamp(
+    timestamp: u64,
+    now: SystemTime,
+    max_age: Duration,
+    max_future_skew: Duration,
+) -> Result<(), SignatureError> {
+    // The timestamp comes from the request, so it may not fit a `SystemTime`.
+    let Some(sent) = UNIX_EPOCH.checked_add(Duration::from_secs(timestamp)) else {
+        return Err(SignatureError::TimestampInFuture);
+    };
+    match now.duration_since(sent) {
+        Ok(age) if age > max_age => Err(SignatureError::Expired),
+        Ok(_) => Ok(()),
+        Err(ahead) if ahead.duration() > max_future_skew => Err(SignatureError::TimestampInFuture),
+        Err(_) => Ok(()),
+    }
+}
+
+fn outcome_to_result(outcome: VerificationOutcome) -> BotResult<()> {
+    match outcome {
+        VerificationOutcome::Valid => Ok(()),
+        VerificationOutcome::Missing => Err(SignatureError::Missing.into()),
+        VerificationOutcome::Malformed | VerificationOutcome::Mismatch => {
+            Err(SignatureError::VerificationFailed.into())
+        }
     }
 }
 
@@ -82,6 +1124,553 @@ mod tests {
             .expect("signature should validate");
     }
 
+    #[test]
+    fn derives_stable_key_from_master_secret() {
+        let verifier = WebhookVerifier::from_master_key("master-secret", b"webhook", b"zalo-salt")
+            .expect("derived verifier");
+        let payload = br#"{"event":"ping"}"#;
+        let signature = verifier.sign_payload(payload).expect("signature");
+
+        assert_eq!(
+            signature,
+            "a64bfd46fd6590ae479e86713d424391b15253bbabe5a4dca942bbe0d42ce5ad"
+        );
+        verifier
+            .verify(payload, Some(&signature))
+            .expect("derived signature should validate");
+    }
+
+    #[test]
+    fn config_rotation_list_accepts_every_secret() {
+        let config = WebhookConfig::default().with_secrets(["current", "previous"]);
+        let verifier = WebhookVerifier::from_config(&config).expect("verifier");
+        let payload = b"payload";
+
+        for secret in ["current", "previous"] {
+            let signature = WebhookVerifier::new(secret)
+                .expect("single verifier")
+                .sign_payload(payload)
+                .expect("signature");
+            verifier
+                .verify(payload, Some(&signature))
+                .expect("rotated secret should validate");
+        }
+        assert_eq!(
+            verifier.sign_payload(payload).expect("primary signature"),
+            WebhookVerifier::new("current")
+                .expect("primary verifier")
+                .sign_payload(payload)
+                .expect("signature")
+        );
+    }
+
+    #[test]
+    fn from_config_decodes_base64_secret() {
+        let config = WebhookConfig::default().with_secret_base64("c2VjcmV0");
+        let verifier = WebhookVerifier::from_config(&config).expect("base64 secret");
+
+        assert_eq!(verifier, WebhookVerifier::new("secret").expect("verifier"));
+    }
+
+    #[test]
+    fn from_config_requires_a_secret() {
+        let error =
+            WebhookVerifier::from_config(&WebhookConfig::default()).expect_err("missing secret");
+
+        assert!(matches!(
+            error,
+            BotError::Signature(SignatureError::MissingSecret)
+        ));
+    }
+
+    #[test]
+    fn verifies_multipart_body_and_iterates_parts() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let body = b"--zalo\r\n\
+Content-Disposition: form-data; name=\"file\"; filename=\"a.png\"\r\n\
+Content-Type: image/png\r\n\
+\r\n\
+PNGDATA\r\n\
+--zalo--\r\n";
+        let signature = verifier.sign_payload(body).expect("signature");
+
+        let parts = verifier
+            .verify_multipart(body, "zalo", Some(&signature))
+            .expect("signature should validate")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("well-formed body");
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].filename(), Some("a.png"));
+        assert_eq!(parts[0].body(), b"PNGDATA");
+
+        let error = verifier
+            .verify_multipart(body, "zalo", Some(&"00".repeat(SIGNATURE_LEN)))
+            .expect_err("tampered signature");
+        assert!(matches!(
+            error,
+            BotError::Signature(SignatureError::VerificationFailed)
+        ));
+    }
+
+    #[test]
+    fn fingerprints_match_only_for_identical_secrets() {
+        let first = WebhookVerifier::new("secret").expect("verifier");
+        let same = WebhookVerifier::new("secret").expect("verifier");
+        let other = WebhookVerifier::new("other-secret").expect("verifier");
+
+        assert_eq!(first.secret_fingerprint(), same.secret_fingerprint());
+        assert_ne!(first.secret_fingerprint(), other.secret_fingerprint());
+        assert_eq!(first.secret_fingerprint().len(), 2 * FINGERPRINT_LEN);
+    }
+
+    #[test]
+    fn detects_algorithm_from_signature_length() {
+        let verifier = WebhookVerifier::new("secret")
+            .expect("verifier")
+            .with_algorithms(SignatureAlgorithm::ALL);
+
+        for algorithm in SignatureAlgorithm::ALL {
+            let signature =
+                zalo_signing::sign_with(algorithm, b"secret", b"payload").expect("signature");
+            assert_eq!(verifier.algorithm_for_len(signature.len()), Some(algorithm));
+            verifier
+                .verify(b"payload", Some(&zalo_signing::encode_hex(signature)))
+                .expect("signature should validate");
+        }
+    }
+
+    #[test]
+    fn round_trips_and_isolates_each_algorithm() {
+        for algorithm in SignatureAlgorithm::ALL {
+            let verifier = WebhookVerifier::with_algorithm("secret", algorithm).expect("verifier");
+            let signature = verifier.sign_payload(b"payload").expect("signature");
+            verifier
+                .verify(b"payload", Some(&signature))
+                .expect("signature should validate");
+
+            for other in SignatureAlgorithm::ALL {
+                if other == algorithm {
+                    continue;
+                }
+                let foreign = WebhookVerifier::with_algorithm("secret", other)
+                    .expect("verifier")
+                    .sign_payload(b"payload")
+                    .expect("signature");
+                assert!(matches!(
+                    verifier.verify(b"payload", Some(&foreign)),
+                    Err(BotError::Signature(SignatureError::VerificationFailed))
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn verifies_prefixed_signature_headers() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let signature = verifier.sign_payload(b"payload").expect("signature");
+
+        for header in [
+            format!("sha256={signature}"),
+            format!(" SHA256 = {signature} "),
+            signature.clone(),
+        ] {
+            verifier
+                .verify_header(b"payload", Some(&header))
+                .expect("header should validate");
+        }
+        assert!(matches!(
+            verifier.verify_header(b"tampered", Some(&format!("sha256={signature}"))),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+        for header in [format!("sha512={signature}"), format!("md5={signature}")] {
+            assert!(matches!(
+                verifier.verify_header(b"payload", Some(&header)),
+                Err(BotError::Signature(SignatureError::AlgorithmMismatch(_)))
+            ));
+        }
+
+        let both = verifier
+            .clone()
+            .with_algorithms([SignatureAlgorithm::Sha1, SignatureAlgorithm::Sha256]);
+        let sha1 = zalo_signing::sign_with(SignatureAlgorithm::Sha1, b"secret", b"payload")
+            .expect("signature");
+        let mislabelled = format!("sha256={}", zalo_signing::encode_hex(sha1));
+        assert!(matches!(
+            both.verify_header(b"payload", Some(&mislabelled)),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+    }
+
+    #[test]
+    fn rejects_unaccepted_or_unknown_signature_lengths() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let sha1 = zalo_signing::sign_with(SignatureAlgorithm::Sha1, b"secret", b"payload")
+            .expect("signature");
+
+        for signature in [zalo_signing::encode_hex(sha1), "00".repeat(16)] {
+            let error = verifier
+                .verify(b"payload", Some(&signature))
+                .expect_err("signature must be rejected");
+
+            assert!(matches!(
+                error,
+                BotError::Signature(SignatureError::VerificationFailed)
+            ));
+        }
+    }
+
+    #[test]
+    fn repeated_verification_hits_cache() {
+        let verifier = WebhookVerifier::new("secret")
+            .expect("verifier")
+            .with_verification_cache(8);
+        let signature = verifier.sign_payload(b"payload").expect("signature");
+        let cache = verifier.cache.clone().expect("cache enabled");
+
+        verifier
+            .verify(b"payload", Some(&signature))
+            .expect("first verification");
+        assert_eq!(cache.hits(), 0);
+        verifier
+            .verify(b"payload", Some(&signature))
+            .expect("cached verification");
+        assert_eq!(cache.hits(), 1);
+
+        let error = verifier
+            .verify(b"other payload", Some(&signature))
+            .expect_err("different payload must miss and fail");
+        assert!(matches!(
+            error,
+            BotError::Signature(SignatureError::VerificationFailed)
+        ));
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn detailed_verification_reports_each_outcome() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let signature = verifier.sign_payload(b"payload").expect("signature");
+        let cases = [
+            (Some(signature.as_str()), VerificationOutcome::Valid),
+            (None, VerificationOutcome::Missing),
+            (Some("not hex"), VerificationOutcome::Malformed),
+            (Some("deadbeef"), VerificationOutcome::Malformed),
+        ];
+
+        for (header, expected) in cases {
+            assert_eq!(verifier.verify_detailed(b"payload", header), expected);
+        }
+        assert_eq!(
+            verifier.verify_detailed(b"tampered", Some(&signature)),
+            VerificationOutcome::Mismatch
+        );
+    }
+
+    #[test]
+    fn decodes_encoded_secrets() {
+        let raw = WebhookVerifier::new("secret").expect("verifier");
+
+        assert_eq!(
+            WebhookVerifier::new_from_hex("736563726574").expect("hex secret"),
+            raw
+        );
+        assert_eq!(
+            WebhookVerifier::new_from_base64("c2VjcmV0").expect("base64 secret"),
+            raw
+        );
+    }
+
+    #[test]
+    fn debug_output_hides_secrets() {
+        let verifier = WebhookVerifier::from_master_key("master-secret", b"webhook", b"zalo-salt")
+            .expect("verifier");
+        let derived = format!("{:?}", verifier.primary_secret());
+        let output = format!("{verifier:?}");
+
+        assert!(output.contains(REDACTED), "{output}");
+        assert!(
+            !output.contains(derived.trim_matches(['[', ']'])),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_encoded_secrets() {
+        for result in [
+            WebhookVerifier::new_from_hex("7365637"),
+            WebhookVerifier::new_from_hex("not hex"),
+            WebhookVerifier::new_from_base64("c2Vj*mV0"),
+        ] {
+            assert!(matches!(
+                result,
+                Err(SignatureError::InvalidSecretLength(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn verifies_composite_prefix_and_body() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let signature = verifier
+            .sign_payload(br#"app-42.{"event":"ping"}"#)
+            .expect("signature");
+
+        verifier
+            .verify_composite(b"app-42", br#"{"event":"ping"}"#, Some(&signature))
+            .expect("composite signature should validate");
+        let error = verifier
+            .verify_composite(b"app-43", br#"{"event":"ping"}"#, Some(&signature))
+            .expect_err("different prefix");
+        assert!(matches!(
+            error,
+            BotError::Signature(SignatureError::VerificationFailed)
+        ));
+    }
+
+    #[test]
+    fn clock_skew_check_distinguishes_past_and_future() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let max_age = Duration::from_secs(300);
+        let max_future_skew = Duration::from_secs(30);
+        let check = |timestamp: u64| {
+            let message = format!("{timestamp}.{{}}");
+            let signature = verifier.sign_payload(message.as_bytes()).expect("sign");
+            verifier.verify_with_clock_skew(
+                b"{}",
+                Some(&signature),
+                timestamp,
+                now,
+                max_age,
+                max_future_skew,
+            )
+        };
+
+        check(1_700_000_000 - 300).expect("past within age");
+        assert!(matches!(
+            check(1_700_000_000 - 301),
+            Err(BotError::Signature(SignatureError::Expired))
+        ));
+        check(1_700_000_000 + 30).expect("future within skew");
+        for timestamp in [1_700_000_000 + 31, u64::MAX] {
+            assert!(matches!(
+                check(timestamp),
+                Err(BotError::Signature(SignatureError::TimestampInFuture))
+            ));
+        }
+    }
+
+    #[test]
+    fn timestamp_tolerance_rejects_replays_and_tampering() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let tolerance = Duration::from_secs(300);
+        let body = br#"{"event":"ping"}"#;
+        let sign = |timestamp: u64| {
+            let mut message = format!("{timestamp}.").into_bytes();
+            message.extend_from_slice(body);
+            verifier.sign_payload(&message).expect("sign")
+        };
+
+        let skewed = sign(1_700_000_020);
+        verifier
+            .verify_with_timestamp(body, Some(&skewed), 1_700_000_020, now, tolerance)
+            .expect("future timestamp within tolerance");
+        let stale = sign(1_000_000_000);
+        assert!(matches!(
+            verifier.verify_with_timestamp(body, Some(&stale), 1_000_000_000, now, tolerance),
+            Err(BotError::Signature(SignatureError::Expired))
+        ));
+
+        let fresh = sign(1_699_999_990);
+        verifier
+            .verify_with_timestamp(body, Some(&fresh), 1_699_999_990, now, tolerance)
+            .expect("fresh timestamp");
+        assert!(matches!(
+            verifier.verify_with_timestamp(body, Some(&fresh), 1_699_999_991, now, tolerance),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+        assert!(matches!(
+            verifier.verify_with_timestamp(
+                br#"{"event":"pong"}"#,
+                Some(&fresh),
+                1_699_999_990,
+                now,
+                tolerance
+            ),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+    }
+
+    #[test]
+    fn time_bucketed_keys_honour_skew() {
+        let verifier = WebhookVerifier::new("master").expect("verifier");
+        let at = |secs: u64| UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let sent = at(1_700_000_010);
+        let signature = verifier
+            .sign_time_bucketed(b"payload", sent, 30)
+            .expect("signature");
+
+        // Same bucket, no skew allowed.
+        verifier
+            .verify_time_bucketed(b"payload", Some(&signature), at(1_700_000_019), 30, 0)
+            .expect("in-bucket signature");
+        // Adjacent buckets on either side, within one bucket of skew.
+        for received in [at(1_700_000_045), at(1_699_999_995)] {
+            verifier
+                .verify_time_bucketed(b"payload", Some(&signature), received, 30, 1)
+                .expect("adjacent-bucket signature");
+            assert!(matches!(
+                verifier.verify_time_bucketed(b"payload", Some(&signature), received, 30, 0),
+                Err(BotError::Signature(SignatureError::VerificationFailed))
+            ));
+        }
+        // Two buckets away is outside a skew of one.
+        assert!(matches!(
+            verifier.verify_time_bucketed(b"payload", Some(&signature), at(1_700_000_075), 30, 1),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+        // The bucketed key never equals the master secret.
+        assert!(verifier.verify(b"payload", Some(&signature)).is_err());
+        assert!(matches!(
+            verifier.verify_time_bucketed(
+                b"payload",
+                Some(&signature),
+                sent,
+                30,
+                MAX_SKEW_BUCKETS + 1
+            ),
+            Err(BotError::Signature(SignatureError::SkewTooLarge(_)))
+        ));
+    }
+
+    #[test]
+    fn time_bucketed_signatures_round_trip_per_algorithm() {
+        let sent = UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_010);
+        for algorithm in SignatureAlgorithm::ALL {
+            let verifier = WebhookVerifier::with_algorithm("master", algorithm).expect("verifier");
+            let signature = verifier
+                .sign_time_bucketed(b"payload", sent, 30)
+                .expect("signature");
+
+            assert_eq!(signature.len(), algorithm.signature_len() * 2);
+            verifier
+                .verify_time_bucketed(b"payload", Some(&signature), sent, 30, 0)
+                .expect("round trip");
+        }
+    }
+
+    #[test]
+    fn verifies_signed_field_subset() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let body = serde_json::json!({
+            "app_id": "123",
+            "event_name": "user_send_text",
+            "timestamp": 1_700_000_000_u64,
+            "message": { "text": "not signed" }
+        });
+        let signature = verifier
+            .sign_payload(b"user_send_text.123.1700000000")
+            .expect("signature");
+        let fields = ["event_name", "app_id", "timestamp"];
+
+        verifier
+            .verify_fields(&body, &fields, Some(&signature))
+            .expect("field subset signature");
+        assert!(matches!(
+            verifier.verify_fields(
+                &body,
+                &["app_id", "event_name", "timestamp"],
+                Some(&signature)
+            ),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+
+        let error = verifier
+            .verify_fields(&body, &["event_name", "user_id"], Some(&signature))
+            .expect_err("missing field");
+        assert!(matches!(
+            error,
+            BotError::Signature(SignatureError::MissingField(ref field)) if field == "user_id"
+        ));
+    }
+
+    #[test]
+    fn envelope_carries_parsed_event_and_raw_bytes() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        #[serde(tag = "event_name", rename_all = "snake_case")]
+        enum Event {
+            UserSendText { message: String },
+            Follow,
+        }
+
+        let payload = br#"{ "event_name": "user_send_text", "message": "hi" }"#;
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let signature = verifier.sign_payload(payload).expect("signature");
+        let before = Instant::now();
+
+        let envelope = verifier
+            .verify_and_envelope::<Event>(payload, Some(&signature))
+            .expect("envelope");
+        assert_eq!(
+            envelope.event(),
+            &Event::UserSendText {
+                message: "hi".to_owned()
+            }
+        );
+        assert_eq!(envelope.raw(), payload);
+        assert!(envelope.verified_at() >= before);
+
+        let tampered = br#"{ "event_name": "follow" }"#;
+        assert!(matches!(
+            verifier.verify_and_envelope::<Event>(tampered, Some(&signature)),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+        let unknown = br#"{ "event_name": "unknown" }"#;
+        let signature = verifier.sign_payload(unknown).expect("signature");
+        assert!(matches!(
+            verifier.verify_and_envelope::<Event>(unknown, Some(&signature)),
+            Err(BotError::Payload(_))
+        ));
+    }
+
+    #[test]
+    fn signs_and_verifies_until_dropped() {
+        let verifier = WebhookVerifier::with_secrets(["current", "previous"]).expect("verifier");
+        let clone = verifier.clone();
+        let signature = verifier.sign_payload(b"payload").expect("signature");
+        drop(verifier);
+
+        // The clone owns its own copy of the secrets.
+        clone
+            .verify(b"payload", Some(&signature))
+            .expect("clone still verifies");
+        drop(clone);
+    }
+
+    #[test]
+    fn accepts_any_matching_candidate_signature() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let valid = verifier.sign_payload(b"payload").expect("signature");
+        let foreign = WebhookVerifier::new("other")
+            .expect("verifier")
+            .sign_payload(b"payload")
+            .expect("signature");
+
+        verifier
+            .verify_any(b"payload", &[&foreign, "zz", &valid])
+            .expect("one valid candidate");
+        assert!(matches!(
+            verifier.verify_any(b"payload", &[&foreign, "zz"]),
+            Err(BotError::Signature(SignatureError::VerificationFailed))
+        ));
+        assert!(matches!(
+            verifier.verify_any(b"payload", &[]),
+            Err(BotError::Signature(SignatureError::Missing))
+        ));
+    }
+
     #[test]
     fn rejects_missing_signature() {
         let verifier = WebhookVerifier::new("secret").expect("verifier");
@@ -106,4 +1695,51 @@ mod tests {
             crate::error::BotError::Signature(SignatureError::VerificationFailed)
         ));
     }
+
+    #[test]
+    fn malformed_and_wrong_signatures_fail_identically() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let wrong = "00".repeat(SIGNATURE_LEN);
+        let malformed = "zz".repeat(SIGNATURE_LEN);
+
+        for signature in [wrong.as_str(), malformed.as_str()] {
+            let error = verifier
+                .verify(b"payload", Some(signature))
+                .expect_err("signature must be rejected");
+
+            assert!(matches!(
+                error,
+                crate::error::BotError::Signature(SignatureError::VerificationFailed)
+            ));
+        }
+    }
+
+    #[test]
+    fn rate_limits_valid_bursts_per_source() {
+        let verifier = WebhookVerifier::new("secret").expect("verifier");
+        let limiter = crate::rate_limit::FixedWindowRateLimiter::new(2, Duration::from_secs(3600));
+        let signature = verifier.sign_payload(b"payload").expect("signature");
+
+        for _ in 0..2 {
+            verifier
+                .verify_and_rate_limit(b"payload", Some(&signature), "10.0.0.1", &limiter)
+                .expect("within budget");
+        }
+        assert!(matches!(
+            verifier.verify_and_rate_limit(b"payload", Some(&signature), "10.0.0.1", &limiter),
+            Err(BotError::RateLimited(source)) if source == "10.0.0.1"
+        ));
+        verifier
+            .verify_and_rate_limit(b"payload", Some(&signature), "10.0.0.2", &limiter)
+            .expect("other source unaffected");
+        for _ in 0..2 {
+            assert!(matches!(
+                verifier.verify_and_rate_limit(b"payload", Some("00"), "10.0.0.3", &limiter),
+                Err(BotError::Signature(SignatureError::VerificationFailed))
+            ));
+        }
+        verifier
+            .verify_and_rate_limit(b"payload", Some(&signature), "10.0.0.3", &limiter)
+            .expect("failed verification consumes no budget");
+    }
 }
@@ -1,12 +1,51 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zalo_signing::SignatureAlgorithm;
+use zalo_types::REDACTED;
 
 use crate::error::{SdkError, SdkResult};
 
+const APP_ID_KEY: &str = "app_id";
+const OA_ID_KEY: &str = "oa_id";
+const SCOPES_KEY: &str = "scopes";
+const ACCESS_TOKEN_KEY: &str = "access_token";
+const TOKEN_EXPIRES_AT_KEY: &str = "token_expires_at";
+const OA_NAME_KEY: &str = "oa_name";
+const OA_LOGO_URL_KEY: &str = "oa_logo_url";
+
+/// Number of random bytes in a generated `state` nonce.
+const STATE_NONCE_LEN: usize = 16;
+
+/// Separator between the nonce and the signature of a `state` value.
+const STATE_SEPARATOR: char = '.';
+
+/// Largest handshake payload, in bytes, accepted by
+/// [`HandshakePayload::from_reader`] and its [`FromStr`] implementation.
+pub const DEFAULT_MAX_PAYLOAD_LEN: usize = 64 * 1024;
+
 /// Immutable context capturing identifiers required by the mini app runtime.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// The `Debug` output never includes the access token.
+#[derive(Clone, Eq, PartialEq)]
 pub struct MiniAppContext {
     app_id: String,
     oa_id: String,
+    scopes: Vec<String>,
+    token: Option<AccessToken>,
+    oa_display: Option<OaDisplay>,
+}
+
+/// Access token held by the context together with its expiry.
+#[derive(Clone, Eq, PartialEq)]
+struct AccessToken {
+    value: String,
+    expires_at: SystemTime,
 }
 
 impl MiniAppContext {
@@ -37,7 +76,219 @@ impl MiniAppContext {
             return Err(SdkError::InvalidOaId(oa_id));
         }
 
-        Ok(Self { app_id, oa_id })
+        Ok(Self {
+            app_id,
+            oa_id,
+            scopes: Vec::new(),
+            token: None,
+            oa_display: None,
+        })
+    }
+
+    /// Builds a context from a flat string map, as passed by JavaScript bridges.
+    ///
+    /// The map uses the `app_id`, `oa_id` and optional `scopes` keys; scopes
+    /// are comma-separated and normalised like [`with_scopes`](Self::with_scopes).
+    /// An `access_token` must come with `token_expires_at`, a Unix timestamp
+    /// in seconds, and `oa_name` with `oa_logo_url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::MissingField`] when `app_id` or `oa_id` is absent
+    /// or a token has no expiry, [`SdkError::InvalidTokenExpiry`] when the
+    /// expiry is not a number, [`SdkError::InvalidOaDisplay`] when the display
+    /// metadata is invalid, and the same errors as [`new`](Self::new) when an
+    /// identifier is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let map = HashMap::from([
+    ///     ("app_id".to_owned(), "app".to_owned()),
+    ///     ("oa_id".to_owned(), "oa".to_owned()),
+    ///     ("scopes".to_owned(), "scope.userInfo,scope.camera".to_owned()),
+    /// ]);
+    /// let context = MiniAppContext::from_map(&map)?;
+    /// assert!(context.has_scope("scope.camera"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_map(map: &HashMap<String, String>) -> SdkResult<Self> {
+        let field = |key: &str| {
+            map.get(key)
+                .cloned()
+                .ok_or_else(|| SdkError::MissingField(key.to_owned()))
+        };
+        let mut context = Self::new(field(APP_ID_KEY)?, field(OA_ID_KEY)?)?;
+        if let Some(scopes) = map.get(SCOPES_KEY) {
+            context = context.with_scopes(scopes.split(','));
+        }
+        if let Some(token) = map.get(ACCESS_TOKEN_KEY) {
+            let expiry = field(TOKEN_EXPIRES_AT_KEY)?;
+            let seconds = expiry
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| SdkError::InvalidTokenExpiry(expiry.clone()))?;
+            context = context
+                .with_refreshed_token(token.clone(), UNIX_EPOCH + Duration::from_secs(seconds));
+        }
+        if let Some(name) = map.get(OA_NAME_KEY) {
+            context = context.with_oa_display(OaDisplay::new(name, field(OA_LOGO_URL_KEY)?)?);
+        }
+
+        Ok(context)
+    }
+
+    /// Converts the context into a flat string map accepted by
+    /// [`from_map`](Self::from_map).
+    ///
+    /// The `scopes` key is omitted when no scope has been granted, the token
+    /// keys when no token is held, and the display keys when no
+    /// [`OaDisplay`] is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let map = MiniAppContext::new("app", "oa")?.to_map();
+    /// assert_eq!(map["app_id"], "app");
+    /// assert!(!map.contains_key("scopes"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(7);
+        map.insert(APP_ID_KEY.to_owned(), self.app_id.clone());
+        map.insert(OA_ID_KEY.to_owned(), self.oa_id.clone());
+        if !self.scopes.is_empty() {
+            map.insert(SCOPES_KEY.to_owned(), self.scopes.join(","));
+        }
+        if let Some(token) = &self.token {
+            let seconds = token
+                .expires_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            map.insert(ACCESS_TOKEN_KEY.to_owned(), token.value.clone());
+            map.insert(TOKEN_EXPIRES_AT_KEY.to_owned(), seconds.to_string());
+        }
+        if let Some(display) = &self.oa_display {
+            map.insert(OA_NAME_KEY.to_owned(), display.name.clone());
+            map.insert(OA_LOGO_URL_KEY.to_owned(), display.logo_url.clone());
+        }
+        map
+    }
+
+    /// Returns a copy of the context holding a new access token.
+    ///
+    /// The rest of the context is left untouched, so a rotated token does not
+    /// require rebuilding and revalidating the identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let now = SystemTime::now();
+    /// let context = MiniAppContext::new("app", "oa")?
+    ///     .with_refreshed_token("token-2", now + Duration::from_secs(3600));
+    /// assert_eq!(context.access_token(), Some("token-2"));
+    /// assert!(!context.is_token_expired(now));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_refreshed_token(
+        &self,
+        new_token: impl Into<String>,
+        new_expiry: SystemTime,
+    ) -> Self {
+        Self {
+            token: Some(AccessToken {
+                value: new_token.into(),
+                expires_at: new_expiry,
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the current access token, if any.
+    #[must_use]
+    pub fn access_token(&self) -> Option<&str> {
+        self.token.as_ref().map(|token| token.value.as_str())
+    }
+
+    /// Returns when the current access token expires, if any.
+    #[must_use]
+    pub fn token_expires_at(&self) -> Option<SystemTime> {
+        self.token.as_ref().map(|token| token.expires_at)
+    }
+
+    /// Returns `true` when no token is held or the token expired at or
+    /// before `now`.
+    ///
+    /// The caller supplies the current time, which keeps the check usable on
+    /// targets without a system clock.
+    #[must_use]
+    pub fn is_token_expired(&self, now: SystemTime) -> bool {
+        self.token
+            .as_ref()
+            .is_none_or(|token| token.expires_at <= now)
+    }
+
+    /// Returns a copy of the context carrying the granted permission scopes.
+    ///
+    /// Scopes are trimmed, empty entries are dropped and duplicates are
+    /// removed while preserving the order of first occurrence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let context = MiniAppContext::new("app", "oa")?
+    ///     .with_scopes(["scope.userInfo", " ", "scope.userInfo"]);
+    /// assert_eq!(context.scopes(), ["scope.userInfo"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = normalize_scopes(scopes);
+        self
+    }
+
+    /// Returns a copy of the context carrying OA display metadata for the
+    /// host chrome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::{MiniAppContext, OaDisplay};
+    ///
+    /// let display = OaDisplay::new("Zalo Shop", "https://cdn.example.com/logo.png")?;
+    /// let context = MiniAppContext::new("app", "oa")?.with_oa_display(display);
+    /// assert_eq!(context.oa_display().map(OaDisplay::name), Some("Zalo Shop"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_oa_display(mut self, display: OaDisplay) -> Self {
+        self.oa_display = Some(display);
+        self
+    }
+
+    /// Returns the OA display metadata, if set.
+    #[must_use]
+    pub fn oa_display(&self) -> Option<&OaDisplay> {
+        self.oa_display.as_ref()
     }
 
     /// Returns the configured application identifier.
@@ -52,6 +303,30 @@ impl MiniAppContext {
         &self.oa_id
     }
 
+    /// Returns the granted permission scopes.
+    #[must_use]
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// Returns `true` when the provided scope has been granted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let context = MiniAppContext::new("app", "oa")?.with_scopes(["scope.userInfo"]);
+    /// assert!(context.has_scope("scope.userInfo"));
+    /// assert!(!context.has_scope("scope.userPhonenumber"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn has_scope(&self, name: &str) -> bool {
+        let name = name.trim();
+        self.scopes.iter().any(|scope| scope == name)
+    }
+
     /// Produces a handshake payload suitable for serialisation.
     ///
     /// # Examples
@@ -69,18 +344,352 @@ impl MiniAppContext {
         HandshakePayload {
             app_id: self.app_id.clone(),
             oa_id: self.oa_id.clone(),
+            scopes: self.scopes.clone(),
+            oa_display: self.oa_display.clone(),
+        }
+    }
+
+    /// Checks that the `(app_id, oa_id)` pair is on the host allowlist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::Unauthorized`] when no entry matches both
+    /// identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let allowed = [("app".to_owned(), "oa".to_owned())];
+    /// MiniAppContext::new("app", "oa")?.validate_against(&allowed)?;
+    /// assert!(MiniAppContext::new("app", "other-oa")?.validate_against(&allowed).is_err());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_against(&self, allowed: &[(String, String)]) -> SdkResult<()> {
+        let permitted = allowed
+            .iter()
+            .any(|(app_id, oa_id)| *app_id == self.app_id && *oa_id == self.oa_id);
+        if !permitted {
+            return Err(SdkError::Unauthorized {
+                app_id: self.app_id.clone(),
+                oa_id: self.oa_id.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Generates a signed anti-CSRF `state` parameter for an OAuth-style
+    /// redirect.
+    ///
+    /// The value is `<nonce>.<signature>`: a random hex nonce followed by the
+    /// HMAC-SHA256 of the app id, OA id and nonce under `secret`. Pass it back
+    /// to [`verify_state`](Self::verify_state) when the flow returns. Replay
+    /// and expiry tracking are left to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::RandomUnavailable`] when the platform random number
+    /// generator fails and [`SdkError::InvalidSecret`] when the HMAC
+    /// implementation rejects `secret`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let context = MiniAppContext::new("app", "oa")?;
+    /// let state = context.generate_state(b"state-secret")?;
+    /// context.verify_state(&state, b"state-secret")?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_state(&self, secret: impl AsRef<[u8]>) -> SdkResult<String> {
+        let mut nonce = [0_u8; STATE_NONCE_LEN];
+        getrandom::getrandom(&mut nonce)?;
+        let nonce = zalo_signing::encode_hex(nonce);
+        let signature = zalo_signing::sign_parts(
+            SignatureAlgorithm::Sha256,
+            secret.as_ref(),
+            &self.state_parts(&nonce),
+        )?;
+
+        Ok(format!(
+            "{nonce}{STATE_SEPARATOR}{}",
+            zalo_signing::encode_hex(signature)
+        ))
+    }
+
+    /// Checks that `state` was produced by
+    /// [`generate_state`](Self::generate_state) for this context and secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::InvalidState`] when the value is malformed or its
+    /// signature does not match.
+    pub fn verify_state(&self, state: &str, secret: impl AsRef<[u8]>) -> SdkResult<()> {
+        let (nonce, signature) = state
+            .split_once(STATE_SEPARATOR)
+            .ok_or_else(|| SdkError::InvalidState("missing signature".to_owned()))?;
+        let signature = zalo_signing::decode_hex(signature)
+            .ok_or_else(|| SdkError::InvalidState("signature is not hex".to_owned()))?;
+        let valid = zalo_signing::verify_parts(
+            SignatureAlgorithm::Sha256,
+            secret.as_ref(),
+            &self.state_parts(nonce),
+            &signature,
+        )
+        .unwrap_or(false);
+        if !valid {
+            return Err(SdkError::InvalidState("signature mismatch".to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// Signs every field of the context for hand-off to the host.
+    ///
+    /// The signature covers the canonical form of [`to_map`](Self::to_map)
+    /// (keys sorted, serialised as JSON) with HMAC-SHA256, so optional fields
+    /// such as scopes and the token are signed whenever present, and fields
+    /// added to the map later are signed automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::Serialization`] when the fields cannot be
+    /// canonicalised and [`SdkError::InvalidSecret`] when the HMAC
+    /// implementation rejects `secret`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let context = MiniAppContext::new("app", "oa")?.with_scopes(["scope.userInfo"]);
+    /// let signed = context.signed_launch_data(b"launch-secret")?;
+    /// assert_eq!(signed.verify(b"launch-secret")?, context);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn signed_launch_data(&self, secret: impl AsRef<[u8]>) -> SdkResult<SignedLaunchData> {
+        let fields: BTreeMap<String, String> = self.to_map().into_iter().collect();
+        let signature = zalo_signing::sign(secret.as_ref(), &serde_json::to_vec(&fields)?)?;
+
+        Ok(SignedLaunchData {
+            fields,
+            signature: zalo_signing::encode_hex(signature),
+        })
+    }
+
+    /// Returns the message authenticated by a `state` signature.
+    fn state_parts<'a>(&'a self, nonce: &'a str) -> [&'a [u8]; 5] {
+        [
+            self.app_id.as_bytes(),
+            b".",
+            self.oa_id.as_bytes(),
+            b".",
+            nonce.as_bytes(),
+        ]
+    }
+}
+
+impl fmt::Debug for MiniAppContext {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MiniAppContext")
+            .field("app_id", &self.app_id)
+            .field("oa_id", &self.oa_id)
+            .field("scopes", &self.scopes)
+            .field("oa_display", &self.oa_display)
+            .field("token_expires_at", &self.token_expires_at())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Context fields signed by [`MiniAppContext::signed_launch_data`].
+///
+/// The `Debug` output never includes the access token.
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SignedLaunchData {
+    fields: BTreeMap<String, String>,
+    signature: String,
+}
+
+impl SignedLaunchData {
+    /// Returns the signed fields, in the format of [`MiniAppContext::to_map`].
+    #[must_use]
+    pub fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// Returns the hex-encoded HMAC-SHA256 signature.
+    #[must_use]
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Recomputes the signature over every present field and rebuilds the
+    /// context.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::InvalidSignature`] when any field or the signature
+    /// was altered, and the same errors as [`MiniAppContext::from_map`] when
+    /// the fields do not form a valid context.
+    pub fn verify(&self, secret: impl AsRef<[u8]>) -> SdkResult<MiniAppContext> {
+        let signature =
+            zalo_signing::decode_hex(&self.signature).ok_or(SdkError::InvalidSignature)?;
+        let canonical = serde_json::to_vec(&self.fields)?;
+        let valid = zalo_signing::verify(secret.as_ref(), &canonical, &signature).unwrap_or(false);
+        if !valid {
+            return Err(SdkError::InvalidSignature);
         }
+
+        MiniAppContext::from_map(&self.fields.clone().into_iter().collect())
     }
 }
 
+impl fmt::Debug for SignedLaunchData {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: BTreeMap<&str, &str> = self
+            .fields
+            .iter()
+            .map(|(key, value)| match key.as_str() {
+                ACCESS_TOKEN_KEY => (key.as_str(), REDACTED),
+                _ => (key.as_str(), value.as_str()),
+            })
+            .collect();
+        formatter
+            .debug_struct("SignedLaunchData")
+            .field("fields", &fields)
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+/// OA name and logo shown by the host around the mini app.
+///
+/// Both fields are checked on construction and deserialisation: the name must
+/// not be blank and the logo must be an absolute `http(s)` URL with a host
+/// and no whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_sdk::{OaDisplay, SdkError};
+///
+/// let display = OaDisplay::new("Zalo Shop", "https://cdn.example.com/logo.png")?;
+/// assert_eq!(display.logo_url(), "https://cdn.example.com/logo.png");
+/// assert!(matches!(OaDisplay::new("Zalo Shop", "logo.png"), Err(SdkError::InvalidOaDisplay(_))));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "OaDisplayFields")]
+pub struct OaDisplay {
+    name: String,
+    logo_url: String,
+}
+
+/// Unvalidated form of [`OaDisplay`] used for deserialisation.
+#[derive(Deserialize)]
+struct OaDisplayFields {
+    name: String,
+    logo_url: String,
+}
+
+impl TryFrom<OaDisplayFields> for OaDisplay {
+    type Error = SdkError;
+
+    fn try_from(fields: OaDisplayFields) -> Result<Self, Self::Error> {
+        Self::new(fields.name, fields.logo_url)
+    }
+}
+
+impl OaDisplay {
+    /// Builds the display metadata after validating both fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::InvalidOaDisplay`] when the name is blank or the
+    /// logo URL is malformed.
+    pub fn new(name: impl Into<String>, logo_url: impl Into<String>) -> SdkResult<Self> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(SdkError::InvalidOaDisplay("name is empty".to_owned()));
+        }
+
+        let logo_url = logo_url.into();
+        if !is_web_url(&logo_url) {
+            return Err(SdkError::InvalidOaDisplay(format!(
+                "logo url `{logo_url}` is not an http(s) url"
+            )));
+        }
+
+        Ok(Self { name, logo_url })
+    }
+
+    /// Returns the OA display name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the OA logo URL.
+    #[must_use]
+    pub fn logo_url(&self) -> &str {
+        &self.logo_url
+    }
+}
+
+/// Returns `true` for an absolute `http` or `https` URL with a non-empty host
+/// and no whitespace.
+fn is_web_url(url: &str) -> bool {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+
+    !host.is_empty() && !url.contains(char::is_whitespace)
+}
+
 /// JSON-serialisable handshake payload shared with the host container.
+///
+/// Optional fields are omitted from the serialised form when unset and default
+/// to empty when absent from incoming JSON, keeping payloads compact for
+/// strict hosts.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct HandshakePayload {
     app_id: String,
     oa_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    scopes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oa_display: Option<OaDisplay>,
 }
 
 impl HandshakePayload {
+    /// Starts building a payload without going through a [`MiniAppContext`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::HandshakePayload;
+    ///
+    /// let payload = HandshakePayload::builder()
+    ///     .app_id("app")
+    ///     .oa_id("oa")
+    ///     .scopes(["scope.userInfo"])
+    ///     .build()?;
+    /// assert_eq!(payload.scopes(), ["scope.userInfo"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn builder() -> HandshakePayloadBuilder {
+        HandshakePayloadBuilder::default()
+    }
+
     /// Returns the application identifier included in the payload.
     #[must_use]
     pub fn app_id(&self) -> &str {
@@ -92,6 +701,232 @@ impl HandshakePayload {
     pub fn oa_id(&self) -> &str {
         &self.oa_id
     }
+
+    /// Returns the permission scopes included in the payload.
+    #[must_use]
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// Returns the OA display metadata included in the payload, if any.
+    #[must_use]
+    pub fn oa_display(&self) -> Option<&OaDisplay> {
+        self.oa_display.as_ref()
+    }
+
+    /// Reads a payload from a JSON byte stream of at most
+    /// [`DEFAULT_MAX_PAYLOAD_LEN`] bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::PayloadTooLarge`] when the stream is longer than
+    /// the limit and [`SdkError::InvalidPayload`] when it cannot be read or
+    /// does not contain a valid payload.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::HandshakePayload;
+    ///
+    /// let payload = HandshakePayload::from_reader(&br#"{"app_id":"app","oa_id":"oa"}"#[..])?;
+    /// assert_eq!(payload.app_id(), "app");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> SdkResult<Self> {
+        Self::from_reader_with_limit(reader, DEFAULT_MAX_PAYLOAD_LEN)
+    }
+
+    /// Reads a payload from a JSON byte stream of at most `limit` bytes.
+    ///
+    /// At most `limit + 1` bytes are read, so an oversized stream is rejected
+    /// without buffering or parsing the rest of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`from_reader`](Self::from_reader).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::{HandshakePayload, SdkError};
+    ///
+    /// let json = br#"{"app_id":"app","oa_id":"oa"}"#;
+    /// let error = HandshakePayload::from_reader_with_limit(&json[..], 8).unwrap_err();
+    /// assert_eq!(error, SdkError::PayloadTooLarge { limit: 8 });
+    /// ```
+    pub fn from_reader_with_limit<R: Read>(reader: R, limit: usize) -> SdkResult<Self> {
+        let mut buffer = Vec::new();
+        reader
+            .take(u64::try_from(limit).unwrap_or(u64::MAX).saturating_add(1))
+            .read_to_end(&mut buffer)
+            .map_err(|error| SdkError::InvalidPayload(error.to_string()))?;
+        if buffer.len() > limit {
+            return Err(SdkError::PayloadTooLarge { limit });
+        }
+
+        serde_json::from_slice(&buffer).map_err(|error| SdkError::InvalidPayload(error.to_string()))
+    }
+
+    /// Parses a payload from a JSON string of at most `limit` bytes.
+    ///
+    /// The length is checked before parsing starts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::PayloadTooLarge`] when the input is longer than
+    /// the limit and [`SdkError::InvalidPayload`] when it is not a valid
+    /// payload.
+    pub fn from_str_with_limit(input: &str, limit: usize) -> SdkResult<Self> {
+        if input.len() > limit {
+            return Err(SdkError::PayloadTooLarge { limit });
+        }
+
+        serde_json::from_str(input).map_err(|error| SdkError::InvalidPayload(error.to_string()))
+    }
+
+    /// Writes the payload as JSON to a byte stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::Serialization`] when writing to the stream fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let mut buffer = Vec::new();
+    /// MiniAppContext::new("app", "oa")?.handshake_payload().to_writer(&mut buffer)?;
+    /// assert_eq!(buffer, br#"{"app_id":"app","oa_id":"oa"}"#);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_writer<W: Write>(&self, writer: W) -> SdkResult<()> {
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Returns a deterministic SHA-256 hex digest of the payload contents.
+    ///
+    /// The digest is computed over canonical JSON (object keys sorted, no
+    /// insignificant whitespace), so it is stable across serialisers and can
+    /// be used as a cache key or `ETag`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::Serialization`] when the payload cannot be
+    /// canonicalised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_sdk::MiniAppContext;
+    ///
+    /// let first = MiniAppContext::new("app", "oa")?.handshake_payload();
+    /// let second = MiniAppContext::new("app", "oa")?.handshake_payload();
+    /// assert_eq!(first.content_hash()?, second.content_hash()?);
+    /// assert_eq!(first.content_hash()?.len(), 64);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn content_hash(&self) -> SdkResult<String> {
+        // `Value` keeps object keys in a sorted map, which yields the
+        // canonical key order regardless of field declaration order.
+        let canonical = serde_json::to_vec(&serde_json::to_value(self)?)?;
+        Ok(zalo_signing::encode_hex(Sha256::digest(canonical)))
+    }
+}
+
+/// Builder returned by [`HandshakePayload::builder`].
+///
+/// Fields are checked when [`build`](Self::build) is called, with the same
+/// rules as [`MiniAppContext::new`] and [`MiniAppContext::with_scopes`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HandshakePayloadBuilder {
+    app_id: Option<String>,
+    oa_id: Option<String>,
+    scopes: Vec<String>,
+    oa_display: Option<OaDisplay>,
+}
+
+impl HandshakePayloadBuilder {
+    /// Sets the application identifier.
+    #[must_use]
+    pub fn app_id(mut self, app_id: impl Into<String>) -> Self {
+        self.app_id = Some(app_id.into());
+        self
+    }
+
+    /// Sets the OA identifier.
+    #[must_use]
+    pub fn oa_id(mut self, oa_id: impl Into<String>) -> Self {
+        self.oa_id = Some(oa_id.into());
+        self
+    }
+
+    /// Sets the permission scopes, replacing any set earlier.
+    #[must_use]
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the OA display metadata.
+    #[must_use]
+    pub fn oa_display(mut self, display: OaDisplay) -> Self {
+        self.oa_display = Some(display);
+        self
+    }
+
+    /// Validates the fields and builds the payload.
+    ///
+    /// Scopes are trimmed and deduplicated, and blank ones are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdkError::MissingField`] when an identifier was not set and
+    /// [`SdkError::InvalidAppId`] or [`SdkError::InvalidOaId`] when it is
+    /// blank.
+    pub fn build(self) -> SdkResult<HandshakePayload> {
+        let app_id = self
+            .app_id
+            .ok_or_else(|| SdkError::MissingField(APP_ID_KEY.to_owned()))?;
+        let oa_id = self
+            .oa_id
+            .ok_or_else(|| SdkError::MissingField(OA_ID_KEY.to_owned()))?;
+        let mut context = MiniAppContext::new(app_id, oa_id)?.with_scopes(self.scopes);
+        context.oa_display = self.oa_display;
+
+        Ok(context.handshake_payload())
+    }
+}
+
+impl FromStr for HandshakePayload {
+    type Err = SdkError;
+
+    /// Parses a payload of at most [`DEFAULT_MAX_PAYLOAD_LEN`] bytes.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_limit(input, DEFAULT_MAX_PAYLOAD_LEN)
+    }
+}
+
+fn normalize_scopes<I, S>(scopes: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut normalized: Vec<String> = Vec::new();
+    for scope in scopes {
+        let scope = scope.into();
+        let trimmed = scope.trim();
+        if trimmed.is_empty() || normalized.iter().any(|existing| existing == trimmed) {
+            continue;
+        }
+        normalized.push(trimmed.to_owned());
+    }
+    normalized
 }
 
 #[cfg(test)]
@@ -105,6 +940,196 @@ mod tests {
         assert!(matches!(error, SdkError::InvalidAppId(_)));
     }
 
+    #[test]
+    fn map_round_trip_preserves_fields() {
+        let context = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_scopes(["scope.userInfo", "scope.camera"]);
+
+        let restored = MiniAppContext::from_map(&context.to_map()).expect("from map");
+
+        assert_eq!(restored, context);
+    }
+
+    #[test]
+    fn refreshed_token_replaces_previous_one() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let context = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_scopes(["scope.userInfo"])
+            .with_refreshed_token("token-1", now);
+
+        let refreshed = context.with_refreshed_token("token-2", now + Duration::from_secs(60));
+
+        assert_eq!(context.access_token(), Some("token-1"));
+        assert_eq!(refreshed.access_token(), Some("token-2"));
+        assert_eq!(refreshed.scopes(), context.scopes());
+        assert!(!format!("{refreshed:?}").contains("token-2"));
+        assert_eq!(
+            MiniAppContext::from_map(&refreshed.to_map()).expect("from map"),
+            refreshed
+        );
+    }
+
+    #[test]
+    fn token_expiry_is_checked_against_now() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let context = MiniAppContext::new("app", "oa").expect("context");
+
+        assert!(context.is_token_expired(now));
+        let context = context.with_refreshed_token("token", now + Duration::from_secs(1));
+        assert!(!context.is_token_expired(now));
+        assert!(context.is_token_expired(now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn from_map_reports_missing_app_id() {
+        let map = HashMap::from([("oa_id".to_owned(), "oa".to_owned())]);
+
+        let error = MiniAppContext::from_map(&map).expect_err("missing app id");
+
+        assert_eq!(error, SdkError::MissingField("app_id".to_owned()));
+    }
+
+    #[test]
+    fn builder_sets_every_payload_field() {
+        let payload = HandshakePayload::builder()
+            .app_id("app")
+            .oa_id("oa")
+            .scopes(["scope.userInfo", " scope.camera ", "scope.userInfo"])
+            .build()
+            .expect("payload");
+
+        assert_eq!(payload.app_id(), "app");
+        assert_eq!(payload.oa_id(), "oa");
+        assert_eq!(payload.scopes(), ["scope.userInfo", "scope.camera"]);
+        assert_eq!(
+            payload,
+            MiniAppContext::new("app", "oa")
+                .expect("context")
+                .with_scopes(["scope.userInfo", "scope.camera"])
+                .handshake_payload()
+        );
+        assert_eq!(
+            HandshakePayload::builder().app_id("app").build(),
+            Err(SdkError::MissingField("oa_id".to_owned()))
+        );
+        assert!(matches!(
+            HandshakePayload::builder().app_id(" ").oa_id("oa").build(),
+            Err(SdkError::InvalidAppId(_))
+        ));
+    }
+
+    #[test]
+    fn signed_launch_data_rejects_tampering_with_any_field() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let context = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_scopes(["scope.userInfo"])
+            .with_refreshed_token("token", now);
+        let signed = context.signed_launch_data(b"secret").expect("signed");
+
+        assert_eq!(signed.verify(b"secret").expect("verified"), context);
+        assert_eq!(signed.verify(b"other"), Err(SdkError::InvalidSignature));
+        assert!(!format!("{signed:?}").contains("\"token\""));
+        assert!(format!("{signed:?}").contains(REDACTED));
+
+        for key in signed.fields().keys() {
+            let mut tampered = signed.clone();
+            tampered
+                .fields
+                .get_mut(key)
+                .expect("present field")
+                .push('x');
+            assert_eq!(
+                tampered.verify(b"secret"),
+                Err(SdkError::InvalidSignature),
+                "{key}"
+            );
+        }
+        let mut removed = signed.clone();
+        removed.fields.remove(SCOPES_KEY);
+        assert_eq!(removed.verify(b"secret"), Err(SdkError::InvalidSignature));
+    }
+
+    #[test]
+    fn oa_display_is_carried_into_handshake_payload() {
+        let display =
+            OaDisplay::new("Zalo Shop", "https://cdn.example.com/logo.png").expect("display");
+        let context = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_oa_display(display.clone());
+
+        let payload = context.handshake_payload();
+        let json = serde_json::to_value(&payload).expect("serialise");
+
+        assert_eq!(payload.oa_display(), Some(&display));
+        assert_eq!(json["oa_display"]["name"], "Zalo Shop");
+        assert_eq!(
+            MiniAppContext::from_map(&context.to_map()).expect("from map"),
+            context
+        );
+    }
+
+    #[test]
+    fn oa_display_rejects_malformed_logo_url() {
+        for url in [
+            "logo.png",
+            "ftp://cdn.example.com/logo.png",
+            "https://",
+            "https://cdn example.com",
+        ] {
+            assert!(
+                matches!(
+                    OaDisplay::new("Zalo Shop", url),
+                    Err(SdkError::InvalidOaDisplay(_))
+                ),
+                "{url}"
+            );
+        }
+        assert!(matches!(
+            OaDisplay::new(" ", "https://cdn.example.com/logo.png"),
+            Err(SdkError::InvalidOaDisplay(_))
+        ));
+        let json =
+            r#"{"app_id":"app","oa_id":"oa","oa_display":{"name":"Shop","logo_url":"logo.png"}}"#;
+        assert!(json.parse::<HandshakePayload>().is_err());
+    }
+
+    #[test]
+    fn payload_round_trips_through_streams() {
+        let payload = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_scopes(["scope.userInfo"])
+            .handshake_payload();
+        let mut buffer = Vec::new();
+
+        payload.to_writer(&mut buffer).expect("write payload");
+        let restored = HandshakePayload::from_reader(buffer.as_slice()).expect("read payload");
+
+        assert_eq!(restored, payload);
+        let error = HandshakePayload::from_reader(&b"{\"app_id\""[..]).expect_err("truncated");
+        assert!(matches!(error, SdkError::InvalidPayload(_)));
+        let error = "not json".parse::<HandshakePayload>().expect_err("invalid");
+        assert!(matches!(error, SdkError::InvalidPayload(_)));
+    }
+
+    #[test]
+    fn content_hash_tracks_payload_contents() {
+        let payload = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .handshake_payload();
+        let reordered: HandshakePayload =
+            serde_json::from_str(r#"{"oa_id":"oa","app_id":"app"}"#).expect("deserialise");
+        let changed = MiniAppContext::new("app", "other-oa")
+            .expect("context")
+            .handshake_payload();
+
+        let hash = payload.content_hash().expect("hash");
+        assert_eq!(hash, reordered.content_hash().expect("hash"));
+        assert_ne!(hash, changed.content_hash().expect("hash"));
+    }
+
     #[test]
     fn produces_handshake_payload() {
         let context = MiniAppContext::new("app", "oa").expect("context");
@@ -115,4 +1140,134 @@ mod tests {
         let json = serde_json::to_string(&payload).expect("serialise");
         assert!(json.contains("\"app_id\":\"app\""));
     }
+
+    #[test]
+    fn omits_unset_optional_fields() {
+        let payload = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .handshake_payload();
+
+        let json = serde_json::to_string(&payload).expect("serialise");
+        assert_eq!(json, r#"{"app_id":"app","oa_id":"oa"}"#);
+
+        let parsed: HandshakePayload = serde_json::from_str(&json).expect("deserialise");
+        assert_eq!(parsed, payload);
+    }
+
+    #[test]
+    fn normalizes_scopes() {
+        let context = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_scopes([
+                " scope.userInfo ",
+                "",
+                "scope.userPhonenumber",
+                "scope.userInfo",
+            ]);
+
+        assert_eq!(
+            context.scopes(),
+            ["scope.userInfo", "scope.userPhonenumber"]
+        );
+    }
+
+    #[test]
+    fn checks_granted_scopes() {
+        let context = MiniAppContext::new("app", "oa")
+            .expect("context")
+            .with_scopes(["scope.userInfo"]);
+
+        assert!(context.has_scope("scope.userInfo"));
+        assert!(!context.has_scope("scope.userPhonenumber"));
+        assert_eq!(context.handshake_payload().scopes(), ["scope.userInfo"]);
+    }
+
+    #[test]
+    fn state_round_trips_and_detects_tampering() {
+        let context = MiniAppContext::new("app", "oa").expect("context");
+        let state = context.generate_state(b"state-secret").expect("state");
+
+        context
+            .verify_state(&state, b"state-secret")
+            .expect("valid state");
+        assert_ne!(
+            state,
+            context.generate_state(b"state-secret").expect("state")
+        );
+
+        let (nonce, signature) = state.split_once('.').expect("signed state");
+        let forged_nonce = format!("{}0", &nonce[..nonce.len() - 1]);
+        let forged_nonce = if forged_nonce == nonce {
+            format!("{}1", &nonce[..nonce.len() - 1])
+        } else {
+            forged_nonce
+        };
+        let tampered = [
+            format!("{forged_nonce}.{signature}"),
+            nonce.to_owned(),
+            format!("{nonce}.zz"),
+        ];
+        for state in &tampered {
+            let error = context
+                .verify_state(state, b"state-secret")
+                .expect_err("tampered state");
+            assert!(matches!(error, SdkError::InvalidState(_)));
+        }
+
+        let other = MiniAppContext::new("other-app", "oa").expect("context");
+        assert!(other.verify_state(&state, b"state-secret").is_err());
+        assert!(context.verify_state(&state, b"other-secret").is_err());
+    }
+
+    #[test]
+    fn enforces_handshake_payload_limit() {
+        let json = r#"{"app_id":"app","oa_id":"oa"}"#;
+
+        let from_reader = HandshakePayload::from_reader_with_limit(json.as_bytes(), json.len())
+            .expect("at-limit payload");
+        let from_str =
+            HandshakePayload::from_str_with_limit(json, json.len()).expect("at-limit payload");
+        assert_eq!(from_reader, from_str);
+        assert_eq!(json.parse::<HandshakePayload>(), Ok(from_str));
+
+        let limit = json.len() - 1;
+        assert_eq!(
+            HandshakePayload::from_reader_with_limit(json.as_bytes(), limit),
+            Err(SdkError::PayloadTooLarge { limit })
+        );
+        assert_eq!(
+            HandshakePayload::from_str_with_limit(json, limit),
+            Err(SdkError::PayloadTooLarge { limit })
+        );
+
+        let oversized = " ".repeat(DEFAULT_MAX_PAYLOAD_LEN) + json;
+        assert_eq!(
+            HandshakePayload::from_reader(oversized.as_bytes()),
+            Err(SdkError::PayloadTooLarge {
+                limit: DEFAULT_MAX_PAYLOAD_LEN
+            })
+        );
+    }
+
+    #[test]
+    fn validates_context_against_allowlist() {
+        let allowed = vec![
+            ("app".to_owned(), "oa".to_owned()),
+            ("other-app".to_owned(), "other-oa".to_owned()),
+        ];
+
+        let context = MiniAppContext::new("app", "oa").expect("context");
+        context.validate_against(&allowed).expect("allowed pair");
+
+        // Both identifiers are known, but not as a pair.
+        let mixed = MiniAppContext::new("app", "other-oa").expect("context");
+        assert_eq!(
+            mixed.validate_against(&allowed),
+            Err(SdkError::Unauthorized {
+                app_id: "app".to_owned(),
+                oa_id: "other-oa".to_owned(),
+            })
+        );
+        assert!(context.validate_against(&[]).is_err());
+    }
 }
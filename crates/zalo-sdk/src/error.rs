@@ -13,11 +13,88 @@ pub enum SdkError {
     /// Provided OA identifier is empty or malformed.
     #[error("invalid oa identifier: {0}")]
     InvalidOaId(String),
+    /// A required field is absent from the input.
+    #[error("missing required field `{0}`")]
+    MissingField(String),
+    /// Token expiry is not a Unix timestamp in seconds.
+    #[error("invalid token expiry: {0}")]
+    InvalidTokenExpiry(String),
+    /// OA display metadata has a blank name or a malformed logo URL.
+    #[error("invalid oa display metadata: {0}")]
+    InvalidOaDisplay(String),
+    /// Anti-CSRF `state` parameter is malformed or its signature is invalid.
+    #[error("invalid state parameter: {0}")]
+    InvalidState(String),
+    /// The app/OA pair is not on the host allowlist.
+    #[error("app `{app_id}` is not allowed for oa `{oa_id}`")]
+    Unauthorized {
+        /// Application identifier of the rejected context.
+        app_id: String,
+        /// OA identifier of the rejected context.
+        oa_id: String,
+    },
+    /// Signed launch data does not match its signature.
+    #[error("launch data signature is invalid")]
+    InvalidSignature,
+    /// Incoming payload exceeds the accepted size.
+    #[error("payload exceeds {limit} bytes")]
+    PayloadTooLarge {
+        /// Maximum accepted payload size in bytes.
+        limit: usize,
+    },
+    /// Incoming payload cannot be read or is not a valid payload.
+    #[error("invalid payload: {0}")]
+    InvalidPayload(String),
+    /// The HMAC implementation rejected the signing secret.
+    #[error("signing secret was rejected")]
+    InvalidSecret,
+    /// The platform random number generator failed.
+    #[error("random number generator failed: {0}")]
+    RandomUnavailable(String),
+    /// A value produced by the SDK could not be serialised.
+    #[error("payload serialisation failed: {0}")]
+    Serialization(String),
+}
+
+impl From<serde_json::Error> for SdkError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+impl From<zalo_signing::InvalidLength> for SdkError {
+    fn from(_: zalo_signing::InvalidLength) -> Self {
+        Self::InvalidSecret
+    }
+}
+
+impl From<getrandom::Error> for SdkError {
+    fn from(error: getrandom::Error) -> Self {
+        Self::RandomUnavailable(error.to_string())
+    }
 }
 
 impl From<SdkError> for AppError {
     fn from(error: SdkError) -> Self {
-        AppError::with(AppErrorKind::Validation, error.to_string())
+        match &error {
+            SdkError::InvalidAppId(_)
+            | SdkError::InvalidOaId(_)
+            | SdkError::MissingField(_)
+            | SdkError::InvalidTokenExpiry(_)
+            | SdkError::InvalidOaDisplay(_)
+            | SdkError::InvalidState(_)
+            | SdkError::PayloadTooLarge { .. }
+            | SdkError::InvalidPayload(_) => {
+                AppError::with(AppErrorKind::Validation, error.to_string())
+            }
+            SdkError::Unauthorized { .. } | SdkError::InvalidSignature => {
+                AppError::with(AppErrorKind::Unauthorized, error.to_string())
+            }
+            SdkError::InvalidSecret => AppError::with(AppErrorKind::Config, error.to_string()),
+            SdkError::RandomUnavailable(_) | SdkError::Serialization(_) => {
+                AppError::with(AppErrorKind::Internal, error.to_string())
+            }
+        }
     }
 }
 
@@ -31,4 +108,31 @@ mod tests {
 
         assert!(matches!(app_error.kind, AppErrorKind::Validation));
     }
+
+    #[test]
+    fn json_error_maps_to_serialization_variant() {
+        let json_error =
+            serde_json::from_str::<serde_json::Value>("{").expect_err("truncated json");
+        let error = SdkError::from(json_error);
+
+        assert!(matches!(error, SdkError::Serialization(_)));
+        let app_error = AppError::from(error);
+        assert!(matches!(app_error.kind, AppErrorKind::Internal));
+    }
+
+    #[test]
+    fn invalid_payload_maps_to_validation_kind() {
+        let app_error = AppError::from(SdkError::InvalidPayload("eof".to_owned()));
+
+        assert!(matches!(app_error.kind, AppErrorKind::Validation));
+    }
+
+    #[test]
+    fn rng_error_maps_to_internal_kind() {
+        let error = SdkError::from(getrandom::Error::UNSUPPORTED);
+
+        assert!(matches!(error, SdkError::RandomUnavailable(_)));
+        let app_error = AppError::from(error);
+        assert!(matches!(app_error.kind, AppErrorKind::Internal));
+    }
 }
@@ -10,5 +10,8 @@ pub mod context;
 /// Error types exposed by the SDK.
 pub mod error;
 
-pub use context::{HandshakePayload, MiniAppContext};
+pub use context::{
+    HandshakePayload, HandshakePayloadBuilder, MiniAppContext, OaDisplay, SignedLaunchData,
+    DEFAULT_MAX_PAYLOAD_LEN,
+};
 pub use error::{SdkError, SdkResult};
@@ -0,0 +1,344 @@
+#![no_std]
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+//! `no_std` HMAC signing primitives for Zalo webhooks.
+//!
+//! The crate only depends on `core` and `alloc`, which makes the signing and
+//! verification logic reusable in constrained environments. Higher-level
+//! helpers such as `zalo_bot::WebhookVerifier` build on these functions.
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+pub use hmac::digest::InvalidLength;
+
+/// Length in bytes of an HMAC-SHA256 signature.
+pub const SIGNATURE_LEN: usize = 32;
+
+/// Hash function backing an HMAC signature.
+///
+/// Each algorithm produces signatures of a distinct length, so the algorithm
+/// can be inferred from a decoded signature when the header does not name it.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_signing::SignatureAlgorithm;
+///
+/// assert_eq!(SignatureAlgorithm::from_signature_len(64), Some(SignatureAlgorithm::Sha512));
+/// assert_eq!(SignatureAlgorithm::Sha1.signature_len(), 20);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SignatureAlgorithm {
+    /// HMAC-SHA1, 20-byte signatures.
+    Sha1,
+    /// HMAC-SHA256, 32-byte signatures.
+    #[default]
+    Sha256,
+    /// HMAC-SHA512, 64-byte signatures.
+    Sha512,
+}
+
+impl SignatureAlgorithm {
+    /// Every supported algorithm.
+    pub const ALL: [Self; 3] = [Self::Sha1, Self::Sha256, Self::Sha512];
+
+    /// Returns the length in bytes of signatures produced by the algorithm.
+    #[must_use]
+    pub const fn signature_len(self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha256 => SIGNATURE_LEN,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// Returns the algorithm producing signatures of the given length.
+    #[must_use]
+    pub fn from_signature_len(len: usize) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.signature_len() == len)
+    }
+
+    /// Returns the lowercase algorithm name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        }
+    }
+}
+
+/// Checks that the secret is accepted as an HMAC-SHA256 key.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// assert!(zalo_signing::check_key(b"secret").is_ok());
+/// ```
+pub fn check_key(secret: &[u8]) -> Result<(), InvalidLength> {
+    <Hmac<Sha256> as KeyInit>::new_from_slice(secret)?;
+    Ok(())
+}
+
+/// Computes the raw HMAC-SHA256 signature of a payload.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// let signature = zalo_signing::sign(b"secret", b"payload")?;
+/// assert_eq!(signature.len(), zalo_signing::SIGNATURE_LEN);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn sign(secret: &[u8], payload: &[u8]) -> Result<Vec<u8>, InvalidLength> {
+    sign_with(SignatureAlgorithm::Sha256, secret, payload)
+}
+
+/// Computes the raw HMAC signature of a payload with the given algorithm.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_signing::SignatureAlgorithm;
+///
+/// let signature = zalo_signing::sign_with(SignatureAlgorithm::Sha1, b"secret", b"payload")?;
+/// assert_eq!(signature.len(), 20);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn sign_with(
+    algorithm: SignatureAlgorithm,
+    secret: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>, InvalidLength> {
+    sign_parts(algorithm, secret, &[payload])
+}
+
+/// Computes the raw HMAC signature of the concatenation of `parts`.
+///
+/// The parts are fed to the MAC in order, so callers signing a composite
+/// message (for example `timestamp.body`) do not need to allocate a combined
+/// buffer.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_signing::SignatureAlgorithm;
+///
+/// let parts: [&[u8]; 3] = [b"12", b".", b"body"];
+/// let signature = zalo_signing::sign_parts(SignatureAlgorithm::Sha256, b"secret", &parts)?;
+/// assert_eq!(signature, zalo_signing::sign(b"secret", b"12.body")?);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn sign_parts(
+    algorithm: SignatureAlgorithm,
+    secret: &[u8],
+    parts: &[&[u8]],
+) -> Result<Vec<u8>, InvalidLength> {
+    match algorithm {
+        SignatureAlgorithm::Sha1 => mac_sign::<Hmac<Sha1>>(secret, parts),
+        SignatureAlgorithm::Sha256 => mac_sign::<Hmac<Sha256>>(secret, parts),
+        SignatureAlgorithm::Sha512 => mac_sign::<Hmac<Sha512>>(secret, parts),
+    }
+}
+
+/// Computes the HMAC-SHA256 signature of a payload as lowercase hex.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// let signature = zalo_signing::sign_hex(b"secret", b"payload")?;
+/// assert_eq!(signature.len(), zalo_signing::SIGNATURE_LEN * 2);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn sign_hex(secret: &[u8], payload: &[u8]) -> Result<String, InvalidLength> {
+    sign(secret, payload).map(encode_hex)
+}
+
+/// Compares a raw signature with the expected one in constant time.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// let signature = zalo_signing::sign(b"secret", b"payload")?;
+/// assert!(zalo_signing::verify(b"secret", b"payload", &signature)?);
+/// assert!(!zalo_signing::verify(b"secret", b"tampered", &signature)?);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn verify(secret: &[u8], payload: &[u8], signature: &[u8]) -> Result<bool, InvalidLength> {
+    verify_with(SignatureAlgorithm::Sha256, secret, payload, signature)
+}
+
+/// Compares a raw signature with the one expected for the given algorithm in
+/// constant time.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_signing::SignatureAlgorithm;
+///
+/// let signature = zalo_signing::sign_with(SignatureAlgorithm::Sha512, b"secret", b"payload")?;
+/// assert!(zalo_signing::verify_with(SignatureAlgorithm::Sha512, b"secret", b"payload", &signature)?);
+/// assert!(!zalo_signing::verify_with(SignatureAlgorithm::Sha256, b"secret", b"payload", &signature)?);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn verify_with(
+    algorithm: SignatureAlgorithm,
+    secret: &[u8],
+    payload: &[u8],
+    signature: &[u8],
+) -> Result<bool, InvalidLength> {
+    verify_parts(algorithm, secret, &[payload], signature)
+}
+
+/// Compares a raw signature with the one expected for the concatenation of
+/// `parts` in constant time.
+///
+/// # Errors
+///
+/// Returns [`InvalidLength`] when the key is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_signing::SignatureAlgorithm;
+///
+/// let signature = zalo_signing::sign(b"secret", b"12.body")?;
+/// let parts: [&[u8]; 3] = [b"12", b".", b"body"];
+/// assert!(zalo_signing::verify_parts(SignatureAlgorithm::Sha256, b"secret", &parts, &signature)?);
+/// # Ok::<_, zalo_signing::InvalidLength>(())
+/// ```
+pub fn verify_parts(
+    algorithm: SignatureAlgorithm,
+    secret: &[u8],
+    parts: &[&[u8]],
+    signature: &[u8],
+) -> Result<bool, InvalidLength> {
+    match algorithm {
+        SignatureAlgorithm::Sha1 => mac_verify::<Hmac<Sha1>>(secret, parts, signature),
+        SignatureAlgorithm::Sha256 => mac_verify::<Hmac<Sha256>>(secret, parts, signature),
+        SignatureAlgorithm::Sha512 => mac_verify::<Hmac<Sha512>>(secret, parts, signature),
+    }
+}
+
+fn mac_sign<M: Mac + KeyInit>(secret: &[u8], parts: &[&[u8]]) -> Result<Vec<u8>, InvalidLength> {
+    let mac = keyed_mac::<M>(secret, parts)?;
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn mac_verify<M: Mac + KeyInit>(
+    secret: &[u8],
+    parts: &[&[u8]],
+    signature: &[u8],
+) -> Result<bool, InvalidLength> {
+    let mac = keyed_mac::<M>(secret, parts)?;
+    Ok(mac.verify_slice(signature).is_ok())
+}
+
+fn keyed_mac<M: Mac + KeyInit>(secret: &[u8], parts: &[&[u8]]) -> Result<M, InvalidLength> {
+    let mut mac = <M as KeyInit>::new_from_slice(secret)?;
+    for part in parts {
+        mac.update(part);
+    }
+    Ok(mac)
+}
+
+/// Encodes bytes as lowercase hex.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(zalo_signing::encode_hex([0xde, 0xad]), "dead");
+/// ```
+#[must_use]
+pub fn encode_hex(bytes: impl AsRef<[u8]>) -> String {
+    hex::encode(bytes)
+}
+
+/// Decodes a hex string, returning `None` when it is malformed.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(zalo_signing::decode_hex("dead"), Some(vec![0xde, 0xad]));
+/// assert_eq!(zalo_signing::decode_hex("zz"), None);
+/// ```
+#[must_use]
+pub fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    hex::decode(input).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4231, test case 2.
+    const KEY: &[u8] = b"Jefe";
+    const DATA: &[u8] = b"what do ya want for nothing?";
+    const EXPECTED: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+
+    #[test]
+    fn signs_known_vector() {
+        assert_eq!(sign_hex(KEY, DATA).expect("signature"), EXPECTED);
+    }
+
+    #[test]
+    fn verifies_known_vector() {
+        let signature = decode_hex(EXPECTED).expect("valid hex");
+
+        assert!(verify(KEY, DATA, &signature).expect("verification"));
+        assert!(!verify(KEY, b"tampered", &signature).expect("verification"));
+    }
+
+    #[test]
+    fn algorithms_round_trip_and_report_their_length() {
+        for algorithm in SignatureAlgorithm::ALL {
+            let signature = sign_with(algorithm, KEY, DATA).expect("signature");
+
+            assert_eq!(signature.len(), algorithm.signature_len());
+            assert_eq!(
+                SignatureAlgorithm::from_signature_len(signature.len()),
+                Some(algorithm)
+            );
+            assert!(verify_with(algorithm, KEY, DATA, &signature).expect("verification"));
+        }
+        assert_eq!(SignatureAlgorithm::from_signature_len(16), None);
+    }
+}
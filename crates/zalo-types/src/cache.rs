@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::config::{AppConfig, ConfigLoader, SharedConfig};
+use crate::error::TypesResult;
+
+/// Configuration loader that serves cached snapshots until explicitly
+/// reloaded.
+///
+/// The configuration is loaded once on construction; [`current`](Self::current)
+/// is a cheap atomic read, and [`reload`](Self::reload) re-runs the wrapped
+/// [`ConfigLoader`] and swaps the snapshot in place. Clones share the same
+/// cache.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_types::{CachedConfigLoader, ConfigLoader};
+///
+/// let cache = CachedConfigLoader::new(ConfigLoader::default())?;
+/// let config = cache.current();
+/// assert_eq!(config.logging().filter(), "info");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct CachedConfigLoader {
+    loader: ConfigLoader,
+    current: Arc<ArcSwap<AppConfig>>,
+}
+
+impl CachedConfigLoader {
+    /// Loads the configuration once and caches the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error produced by [`ConfigLoader::load`].
+    pub fn new(loader: ConfigLoader) -> TypesResult<Self> {
+        let config = loader.load()?;
+
+        Ok(Self {
+            loader,
+            current: Arc::new(ArcSwap::from_pointee(config)),
+        })
+    }
+
+    /// Returns the cached configuration snapshot.
+    #[must_use]
+    pub fn current(&self) -> SharedConfig {
+        self.current.load_full()
+    }
+
+    /// Reloads the configuration from its sources and replaces the cache.
+    ///
+    /// On failure the previous snapshot stays in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error produced by [`ConfigLoader::load`].
+    pub fn reload(&self) -> TypesResult<SharedConfig> {
+        let config = Arc::new(self.loader.load()?);
+        self.current.store(Arc::clone(&config));
+
+        Ok(config)
+    }
+
+    /// Returns the wrapped loader.
+    #[must_use]
+    pub fn loader(&self) -> &ConfigLoader {
+        &self.loader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::write;
+
+    use tempfile::NamedTempFile;
+
+    use crate::config::Environment;
+
+    #[test]
+    fn serves_cached_value_until_reload() {
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), "environment = \"staging\"\n").expect("write config");
+        let loader = ConfigLoader::new("ZALO_CACHE_TEST_").with_file_path(file.path());
+        let cache = CachedConfigLoader::new(loader).expect("initial load");
+
+        write(file.path(), "environment = \"production\"\n").expect("rewrite config");
+        assert_eq!(cache.current().environment(), Environment::Staging);
+
+        let reloaded = cache.reload().expect("reload");
+        assert_eq!(reloaded.environment(), Environment::Production);
+        assert_eq!(cache.current().environment(), Environment::Production);
+    }
+
+    #[test]
+    fn failed_reload_keeps_previous_snapshot() {
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), "environment = \"staging\"\n").expect("write config");
+        let loader = ConfigLoader::new("ZALO_CACHE_TEST_").with_file_path(file.path());
+        let cache = CachedConfigLoader::new(loader).expect("initial load");
+
+        write(file.path(), "environment = 42\n").expect("rewrite config");
+        cache.reload().expect_err("invalid config");
+
+        assert_eq!(cache.current().environment(), Environment::Staging);
+    }
+}
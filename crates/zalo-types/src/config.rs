@@ -1,13 +1,27 @@
-use std::env;
+use std::collections::BTreeMap;
+use std::env::{self, VarError};
+use std::fmt;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use figment::{
     providers::{Env, Format, Serialized, Toml},
-    Figment,
+    value::Dict,
+    Figment, Metadata, Profile, Provider,
 };
-use serde::{Deserialize, Serialize};
+use serde::de::{self, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::level_filters::LevelFilter;
+use tracing::{field, info_span, warn, Span};
+use tracing_subscriber::filter::Directive;
+use tracing_subscriber::EnvFilter;
 
-use crate::error::{ConfigError, TypesError, TypesResult};
+use crate::duration::HumanDuration;
+use crate::error::{ConfigError, ParseLogFormatError, TypesError, TypesResult, REDACTED};
 
 /// Application-level configuration contract.
 ///
@@ -21,11 +35,34 @@ use crate::error::{ConfigError, TypesError, TypesResult};
 /// let config = AppConfig::default();
 /// assert_eq!(config.environment(), Environment::Development);
 /// ```
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default)]
 pub struct AppConfig {
     environment: Environment,
+    strict: bool,
+    shutdown_grace_period: HumanDuration,
     logging: LoggingConfig,
+    webhook: WebhookConfig,
+    retry: RetryConfig,
+}
+
+/// Default time in-flight requests get to finish during graceful shutdown.
+const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Environment variable prefix used by [`ConfigLoader::default`].
+const DEFAULT_ENV_PREFIX: &str = "ZALO_BOT_";
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            environment: Environment::default(),
+            strict: false,
+            shutdown_grace_period: HumanDuration::new(DEFAULT_SHUTDOWN_GRACE_PERIOD),
+            logging: LoggingConfig::default(),
+            webhook: WebhookConfig::default(),
+            retry: RetryConfig::default(),
+        }
+    }
 }
 
 impl AppConfig {
@@ -41,6 +78,136 @@ impl AppConfig {
         &self.logging
     }
 
+    /// Returns the webhook configuration block.
+    #[must_use]
+    pub fn webhook(&self) -> &WebhookConfig {
+        &self.webhook
+    }
+
+    /// Returns the retry policy for outgoing API calls.
+    #[must_use]
+    pub fn retry(&self) -> &RetryConfig {
+        &self.retry
+    }
+
+    /// Returns the exact filter directive used when building the tracing
+    /// subscriber.
+    ///
+    /// This is the configured `logging.filter` with surrounding whitespace
+    /// removed. When the environment caps logging (see
+    /// [`max_level_for_environment`](Self::max_level_for_environment)), every
+    /// directive more verbose than the ceiling is lowered to it; directives
+    /// that cannot be parsed are kept verbatim so that building the subscriber
+    /// still reports them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, Environment, LogFormat, LoggingConfig};
+    ///
+    /// let config = AppConfig::default().with_logging(LoggingConfig::new(" trace ", LogFormat::Text));
+    /// assert_eq!(config.effective_filter(), "trace");
+    ///
+    /// let production = config.with_environment(Environment::Production);
+    /// assert_eq!(production.effective_filter(), "debug");
+    /// ```
+    #[must_use]
+    pub fn effective_filter(&self) -> String {
+        let filter = self.logging.filter().trim();
+        let Some(ceiling) = self.max_level_for_environment() else {
+            return filter.to_owned();
+        };
+
+        filter
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| match parse_directive(directive) {
+                Some((target, level)) if level > ceiling => match target {
+                    Some(target) => format!("{target}={ceiling}"),
+                    None => ceiling.to_string(),
+                },
+                _ => directive.to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Returns the accepted age of a webhook timestamp.
+    ///
+    /// An explicit `webhook.tolerance` wins; otherwise the default of the
+    /// configured [`Environment`] applies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zalo_types::{AppConfig, Environment, WebhookConfig};
+    ///
+    /// let config = AppConfig::default().with_environment(Environment::Production);
+    /// assert_eq!(config.effective_webhook_tolerance(), Duration::from_secs(300));
+    ///
+    /// let webhook = WebhookConfig::default().with_tolerance(Duration::from_secs(30));
+    /// let config = config.with_webhook(webhook);
+    /// assert_eq!(config.effective_webhook_tolerance(), Duration::from_secs(30));
+    /// ```
+    #[must_use]
+    pub fn effective_webhook_tolerance(&self) -> Duration {
+        self.webhook
+            .tolerance()
+            .unwrap_or_else(|| self.environment.default_webhook_tolerance())
+    }
+
+    /// Returns the most verbose level that may be emitted, if the environment
+    /// restricts it.
+    ///
+    /// Production caps logging at `logging.production_max_level` (`debug` by
+    /// default) so that a stray `trace` filter cannot flood the logs with
+    /// verbose records or personal data; other environments are unrestricted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tracing::level_filters::LevelFilter;
+    /// use zalo_types::{AppConfig, Environment};
+    ///
+    /// assert_eq!(AppConfig::default().max_level_for_environment(), None);
+    /// let production = AppConfig::default().with_environment(Environment::Production);
+    /// assert_eq!(production.max_level_for_environment(), Some(LevelFilter::DEBUG));
+    /// ```
+    #[must_use]
+    pub fn max_level_for_environment(&self) -> Option<LevelFilter> {
+        (self.environment == Environment::Production).then(|| self.logging.production_max_level())
+    }
+
+    /// Returns `true` when the global default level of the
+    /// [effective filter](Self::effective_filter) is `debug` or more verbose,
+    /// so a production ceiling below `debug` turns it off.
+    ///
+    /// Only directives without a target count, and the last one wins as it
+    /// does in the subscriber; `zalo_bot=debug` raises a single module and
+    /// therefore does not enable debug logging globally. Levels may be given by
+    /// name or number (`4` is `debug`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, LogFormat, LoggingConfig};
+    ///
+    /// let config = AppConfig::default().with_logging(LoggingConfig::new("debug", LogFormat::Text));
+    /// assert!(config.is_debug_logging());
+    /// assert!(!AppConfig::default().is_debug_logging());
+    /// ```
+    #[must_use]
+    pub fn is_debug_logging(&self) -> bool {
+        self.effective_filter()
+            .split(',')
+            .rev()
+            .filter_map(|directive| parse_directive(directive.trim()))
+            .find_map(|(target, level)| target.is_none().then_some(level))
+            .is_some_and(|level| level >= LevelFilter::DEBUG)
+    }
+
     /// Creates a copy of the configuration with the provided environment.
     ///
     /// # Examples
@@ -57,6 +224,60 @@ impl AppConfig {
         self
     }
 
+    /// Creates a copy of the configuration with a different shutdown drain
+    /// timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zalo_types::AppConfig;
+    ///
+    /// let config = AppConfig::default().with_shutdown_grace_period(Duration::from_secs(5));
+    /// assert_eq!(config.shutdown_grace_period(), Duration::from_secs(5));
+    /// ```
+    #[must_use]
+    pub fn with_shutdown_grace_period(mut self, period: Duration) -> Self {
+        self.shutdown_grace_period = period.into();
+        self
+    }
+
+    /// Returns how long the application should let in-flight requests finish
+    /// during graceful shutdown (30 seconds unless `shutdown_grace_period` is
+    /// set).
+    ///
+    /// Nothing in these crates enforces the value; pass it to the server's
+    /// shutdown timeout.
+    #[must_use]
+    pub fn shutdown_grace_period(&self) -> Duration {
+        self.shutdown_grace_period.as_duration()
+    }
+
+    /// Creates a copy of the configuration with strict validation toggled.
+    ///
+    /// Strict mode adds deployment guardrails such as
+    /// [`enforce_production_logging`](Self::enforce_production_logging) to
+    /// [`validate`](Self::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::AppConfig;
+    ///
+    /// assert!(AppConfig::default().with_strict(true).is_strict());
+    /// ```
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns `true` when strict validation is enabled.
+    #[must_use]
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
     /// Creates a copy of the configuration with custom logging settings.
     ///
     /// # Examples
@@ -73,18 +294,288 @@ impl AppConfig {
         self.logging = logging;
         self
     }
+
+    /// Creates a copy of the configuration with custom webhook settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, WebhookConfig};
+    ///
+    /// let webhook = WebhookConfig::default().with_secret("top-secret");
+    /// let config = AppConfig::default().with_webhook(webhook);
+    /// assert_eq!(config.webhook().secret(), Some("top-secret"));
+    /// ```
+    #[must_use]
+    pub fn with_webhook(mut self, webhook: WebhookConfig) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Creates a copy of the configuration with the provided retry policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, RetryConfig};
+    ///
+    /// let config = AppConfig::default().with_retry(RetryConfig::default().with_max_retries(5));
+    /// assert_eq!(config.retry().max_retries(), 5);
+    /// ```
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Exports the configuration as TOML readable by [`ConfigLoader`].
+    ///
+    /// The output contains every section (`[logging]`, `[webhook]`, `[retry]`) with
+    /// secret fields omitted, so it is safe to print.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TypesError::Other`] when the model cannot be serialised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, WebhookConfig};
+    ///
+    /// let config = AppConfig::default().with_webhook(WebhookConfig::default().with_secret("s3cr3t"));
+    /// let exported = config.to_toml()?;
+    /// assert!(exported.contains("[logging]"));
+    /// assert!(!exported.contains("s3cr3t"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_toml(&self) -> TypesResult<String> {
+        let redacted = self.clone().with_webhook(self.webhook.redacted());
+        toml::to_string_pretty(&redacted).map_err(|error| {
+            TypesError::with_message("failed to serialise configuration as TOML").with_source(error)
+        })
+    }
+
+    /// Rejects plain-text logging in production.
+    ///
+    /// Production log pipelines expect structured records, so `text` there is
+    /// almost always a misconfiguration. [`validate`](Self::validate) runs
+    /// this check when strict mode is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] for `logging.format` when the
+    /// environment is production and the format is [`LogFormat::Text`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, Environment, LogFormat, LoggingConfig};
+    ///
+    /// let config = AppConfig::default()
+    ///     .with_environment(Environment::Production)
+    ///     .with_logging(LoggingConfig::new("info", LogFormat::Text));
+    /// assert!(config.enforce_production_logging().is_err());
+    /// ```
+    pub fn enforce_production_logging(&self) -> Result<(), ConfigError> {
+        if self.environment == Environment::Production && self.logging.format == LogFormat::Text {
+            return Err(ConfigError::Invalid {
+                field: "logging.format".to_owned(),
+                reason: "production requires structured logs (`json` or `bunyan`)".to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates cross-field constraints that cannot be expressed by the
+    /// serde model alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] naming the offending field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, WebhookConfig};
+    ///
+    /// let webhook = WebhookConfig::default()
+    ///     .with_secret("primary")
+    ///     .with_secrets(["primary", "previous"]);
+    /// let config = AppConfig::default().with_webhook(webhook);
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.strict {
+            self.enforce_production_logging()?;
+        }
+        self.logging.validate()?;
+        self.webhook.validate()?;
+        self.retry.validate()
+    }
+
+    /// Lists the keys whose values differ between `self` and `other`, in key
+    /// order.
+    ///
+    /// Keys are dotted paths such as `logging.filter`. Changed webhook secrets
+    /// are reported with their values replaced by [`REDACTED`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TypesError::Other`] when either model cannot be serialised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, Environment};
+    ///
+    /// let current = AppConfig::default();
+    /// let next = current.clone().with_environment(Environment::Staging);
+    /// let changes = current.diff(&next)?;
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].key(), "environment");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn diff(&self, other: &AppConfig) -> TypesResult<Vec<ConfigChange>> {
+        let before = flatten_config(self)?;
+        let mut after = flatten_config(other)?;
+        let mut changes = Vec::new();
+        for (key, old) in before {
+            let new = after.remove(&key);
+            if new.as_ref() != Some(&old) {
+                changes.push(ConfigChange::new(key, Some(old), new));
+            }
+        }
+        changes.extend(
+            after
+                .into_iter()
+                .map(|(key, new)| ConfigChange::new(key, None, Some(new))),
+        );
+        changes.sort_by(|left, right| left.key.cmp(&right.key));
+        Ok(changes)
+    }
+}
+
+/// Splits a filter directive into its selector (target, span and fields) and
+/// level, or returns `None` when it is not a valid directive.
+///
+/// Directives without a selector set the global default level.
+fn parse_directive(directive: &str) -> Option<(Option<String>, LevelFilter)> {
+    // `Directive` exposes no accessors, but its display form is canonical:
+    // `[selector=]level` with the level always after the last `=`.
+    let rendered = directive.parse::<Directive>().ok()?.to_string();
+    match rendered.rsplit_once('=') {
+        Some((selector, level)) => Some((Some(selector.to_owned()), level.parse().ok()?)),
+        None => Some((None, rendered.parse().ok()?)),
+    }
+}
+
+/// Value of a configuration key that differs between two [`AppConfig`]s, as
+/// reported by [`AppConfig::diff`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigChange {
+    key: String,
+    before: Option<JsonValue>,
+    after: Option<JsonValue>,
+}
+
+impl ConfigChange {
+    fn new(key: String, before: Option<JsonValue>, after: Option<JsonValue>) -> Self {
+        let redact = |value: Option<JsonValue>| {
+            if key.starts_with("webhook.secret") {
+                value
+                    .filter(|value| match value {
+                        JsonValue::Null => false,
+                        JsonValue::Array(items) => !items.is_empty(),
+                        _ => true,
+                    })
+                    .map(|_| REDACTED.into())
+            } else {
+                value
+            }
+        };
+        let (before, after) = (redact(before), redact(after));
+
+        Self { key, before, after }
+    }
+
+    /// Returns the dotted key that changed.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the previous value, or `None` when the key was absent.
+    #[must_use]
+    pub fn before(&self) -> Option<&JsonValue> {
+        self.before.as_ref()
+    }
+
+    /// Returns the new value, or `None` when the key is removed.
+    #[must_use]
+    pub fn after(&self) -> Option<&JsonValue> {
+        self.after.as_ref()
+    }
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<JsonValue>| {
+            value
+                .as_ref()
+                .map_or_else(|| "<unset>".to_owned(), ToString::to_string)
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.key,
+            show(&self.before),
+            show(&self.after)
+        )
+    }
+}
+
+/// Serialises `config` into a map from dotted key to leaf value.
+fn flatten_config(config: &AppConfig) -> TypesResult<BTreeMap<String, JsonValue>> {
+    let mut flat = BTreeMap::new();
+    let value = serde_json::to_value(config).map_err(|error| {
+        TypesError::with_message("failed to serialise configuration").with_source(error)
+    })?;
+    let mut pending = vec![(String::new(), value)];
+    while let Some((path, value)) = pending.pop() {
+        match value {
+            JsonValue::Object(fields) => {
+                for (name, value) in fields {
+                    let key = if path.is_empty() {
+                        name
+                    } else {
+                        format!("{path}.{name}")
+                    };
+                    pending.push((key, value));
+                }
+            }
+            value => {
+                flat.insert(path, value);
+            }
+        }
+    }
+    Ok(flat)
 }
 
 /// Deployment environment the service operates in.
 ///
+/// Environments are ordered by progression towards production:
+/// `Development < Staging < Production`.
+///
 /// # Examples
 ///
 /// ```
 /// use zalo_types::Environment;
 ///
 /// assert_eq!(Environment::Production.as_str(), "production");
+/// assert!(Environment::Staging < Environment::Production);
 /// ```
-#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Environment {
     /// Local development (default).
@@ -114,6 +605,44 @@ impl Environment {
             Environment::Production => "production",
         }
     }
+
+    /// Returns `true` when the environment is `other` or later in the
+    /// progression towards production.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::Environment;
+    ///
+    /// assert!(Environment::Production.is_at_least(Environment::Staging));
+    /// assert!(!Environment::Development.is_at_least(Environment::Staging));
+    /// ```
+    #[must_use]
+    pub fn is_at_least(self, other: Environment) -> bool {
+        self >= other
+    }
+
+    /// Returns the webhook timestamp tolerance used when
+    /// `webhook.tolerance` is not configured.
+    ///
+    /// Development allows ten minutes to absorb clock skew and debugger
+    /// pauses; staging and production allow five.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zalo_types::Environment;
+    ///
+    /// assert_eq!(Environment::Production.default_webhook_tolerance(), Duration::from_secs(300));
+    /// ```
+    #[must_use]
+    pub fn default_webhook_tolerance(&self) -> Duration {
+        match self {
+            Environment::Development => Duration::from_secs(10 * 60),
+            Environment::Staging | Environment::Production => Duration::from_secs(5 * 60),
+        }
+    }
 }
 
 /// Logging subsystem configuration.
@@ -128,8 +657,16 @@ impl Environment {
 /// ```
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct LoggingConfig {
-    filter: String,
+    filter: FilterExpr,
     format: LogFormat,
+    #[serde(default)]
+    output: LogOutput,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    directory: Option<PathBuf>,
+    #[serde(default)]
+    span_timing: bool,
+    #[serde(default)]
+    production_max_level: MaxLevel,
 }
 
 impl LoggingConfig {
@@ -146,35 +683,211 @@ impl LoggingConfig {
     #[must_use]
     pub fn new(filter: impl Into<String>, format: LogFormat) -> Self {
         Self {
-            filter: filter.into(),
+            filter: FilterExpr(filter.into()),
             format,
+            output: LogOutput::default(),
+            directory: None,
+            span_timing: false,
+            production_max_level: MaxLevel::default(),
         }
     }
 
-    /// Returns the configured filter expression.
+    /// Returns a copy of the block writing to the provided output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{LogFormat, LogOutput, LoggingConfig};
+    ///
+    /// let logging = LoggingConfig::new("info", LogFormat::Text).with_output(LogOutput::Split);
+    /// assert_eq!(logging.output(), LogOutput::Split);
+    /// ```
     #[must_use]
-    pub fn filter(&self) -> &str {
-        &self.filter
+    pub fn with_output(mut self, output: LogOutput) -> Self {
+        self.output = output;
+        self
     }
 
-    /// Returns the configured logging format.
+    /// Returns a copy of the block writing to standard output and to
+    /// daily-rolling files in `directory`.
+    ///
+    /// This is a shortcut for [`LogOutput::Both`] with the log directory set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{LogFormat, LogOutput, LoggingConfig};
+    ///
+    /// let logging = LoggingConfig::new("info", LogFormat::Json).with_file_output("./logs");
+    /// assert_eq!(logging.output(), LogOutput::Both);
+    /// assert_eq!(logging.directory(), Some(std::path::Path::new("./logs")));
+    /// ```
     #[must_use]
-    pub fn format(&self) -> LogFormat {
-        self.format
+    pub fn with_file_output(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.output = LogOutput::Both;
+        self.directory = Some(directory.into());
+        self
     }
-}
 
-impl Default for LoggingConfig {
-    fn default() -> Self {
-        Self {
-            filter: "info".to_owned(),
-            format: LogFormat::Text,
-        }
+    /// Returns a copy of the block that logs the duration of every closed
+    /// span.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{LogFormat, LoggingConfig};
+    ///
+    /// let logging = LoggingConfig::new("info", LogFormat::Json).with_span_timing(true);
+    /// assert!(logging.span_timing());
+    /// ```
+    #[must_use]
+    pub fn with_span_timing(mut self, enabled: bool) -> Self {
+        self.span_timing = enabled;
+        self
     }
-}
 
-/// Supported output formats for logs.
-///
+    /// Returns a copy of the block capping production logging at `level`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tracing::level_filters::LevelFilter;
+    /// use zalo_types::{LogFormat, LoggingConfig};
+    ///
+    /// let logging = LoggingConfig::new("info", LogFormat::Json).with_production_max_level(LevelFilter::INFO);
+    /// assert_eq!(logging.production_max_level(), LevelFilter::INFO);
+    /// ```
+    #[must_use]
+    pub fn with_production_max_level(mut self, level: LevelFilter) -> Self {
+        self.production_max_level = MaxLevel(level);
+        self
+    }
+
+    /// Returns the most verbose level emitted in production.
+    #[must_use]
+    pub fn production_max_level(&self) -> LevelFilter {
+        self.production_max_level.0
+    }
+
+    /// Returns the configured filter expression.
+    #[must_use]
+    pub fn filter(&self) -> &str {
+        &self.filter.0
+    }
+
+    /// Returns the configured logging format.
+    #[must_use]
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    /// Returns the configured output streams.
+    #[must_use]
+    pub fn output(&self) -> LogOutput {
+        self.output
+    }
+
+    /// Returns whether span durations are logged when spans close.
+    #[must_use]
+    pub fn span_timing(&self) -> bool {
+        self.span_timing
+    }
+
+    /// Returns the directory receiving log files, if any.
+    #[must_use]
+    pub fn directory(&self) -> Option<&Path> {
+        self.directory.as_deref()
+    }
+
+    /// Checks that file output has a directory to write to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] when the output is
+    /// [`LogOutput::Both`] but no directory is configured.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.output == LogOutput::Both && self.directory.is_none() {
+            return Err(ConfigError::Invalid {
+                field: "logging.directory".to_owned(),
+                reason: "required when `logging.output` is `both`".to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            filter: FilterExpr("info".to_owned()),
+            format: LogFormat::Text,
+            output: LogOutput::default(),
+            directory: None,
+            span_timing: false,
+            production_max_level: MaxLevel::default(),
+        }
+    }
+}
+
+/// Level ceiling serialised as a lowercase level name (`off` to `trace`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct MaxLevel(LevelFilter);
+
+impl Default for MaxLevel {
+    fn default() -> Self {
+        Self(LevelFilter::DEBUG)
+    }
+}
+
+impl Serialize for MaxLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for MaxLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let level = String::deserialize(deserializer)?;
+        level
+            .trim()
+            .parse()
+            .map(Self)
+            .map_err(|_| de::Error::custom(format_args!("invalid log level `{level}`")))
+    }
+}
+
+/// `tracing_subscriber::EnvFilter` expression validated on deserialisation.
+///
+/// Invalid directives are rejected while the configuration is extracted
+/// instead of when the tracing dispatcher is built. Values passed to
+/// [`LoggingConfig::new`] are not checked here.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+struct FilterExpr(String);
+
+impl<'de> Deserialize<'de> for FilterExpr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let expression = String::deserialize(deserializer)?;
+        EnvFilter::try_new(expression.trim()).map_err(|error| {
+            de::Error::custom(format_args!("invalid filter `{expression}`: {error}"))
+        })?;
+
+        Ok(Self(expression))
+    }
+}
+
+/// Supported output formats for logs.
+///
 /// # Examples
 ///
 /// ```
@@ -183,187 +896,2280 @@ impl Default for LoggingConfig {
 /// let logging = LoggingConfig::new("info", LogFormat::Json);
 /// assert_eq!(matches!(logging.format(), LogFormat::Json), true);
 /// ```
-#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogFormat {
     /// Plain-text logs optimised for human consumption.
+    #[default]
     Text,
     /// Structured JSON logs suitable for ingestion by log processors.
     Json,
+    /// Bunyan-compatible JSON records (`msg`, numeric `level`, `time`).
+    Bunyan,
+    /// Length-prefixed MessagePack records for compact log shipping.
+    ///
+    /// Rendering them requires the `msgpack` feature of `zalo-bot`.
+    #[serde(rename = "msgpack")]
+    MsgPack,
+}
+
+impl LogFormat {
+    /// Returns the canonical string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::LogFormat;
+    ///
+    /// assert_eq!(LogFormat::Json.as_str(), "json");
+    /// ```
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+            LogFormat::Bunyan => "bunyan",
+            LogFormat::MsgPack => "msgpack",
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = ParseLogFormatError;
+
+    /// Parses a log format case-insensitively.
+    ///
+    /// Besides the canonical names, `structured` is accepted for
+    /// [`LogFormat::Json`], `plain`/`human` for [`LogFormat::Text`] and
+    /// `messagepack` for [`LogFormat::MsgPack`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::LogFormat;
+    ///
+    /// assert_eq!("JSON".parse::<LogFormat>()?, LogFormat::Json);
+    /// assert_eq!("plain".parse::<LogFormat>()?, LogFormat::Text);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "text" | "plain" | "human" => Ok(LogFormat::Text),
+            "json" | "structured" => Ok(LogFormat::Json),
+            "bunyan" => Ok(LogFormat::Bunyan),
+            "msgpack" | "messagepack" => Ok(LogFormat::MsgPack),
+            _ => Err(ParseLogFormatError::new(input)),
+        }
+    }
+}
+
+/// Output streams that log records are written to.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_types::{LogOutput, LoggingConfig};
+///
+/// assert_eq!(LoggingConfig::default().output(), LogOutput::Stdout);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogOutput {
+    /// Every record goes to standard output (default).
+    #[default]
+    Stdout,
+    /// `WARN` and `ERROR` records go to standard error, the rest to standard
+    /// output.
+    Split,
+    /// Every record goes to standard output and to daily-rolling files in
+    /// `logging.directory`.
+    Both,
+    /// Records go to the browser console (`console.log`, `console.error`)
+    /// for code running in a webview. Requires the `wasm` feature of
+    /// `zalo-bot`.
+    Console,
+}
+
+/// Largest accepted `retry.max_retries`.
+pub const MAX_RETRIES_LIMIT: u32 = 10;
+
+/// Retry policy for outgoing Zalo API calls.
+///
+/// Delays grow exponentially from `base_delay` and are capped at
+/// `max_delay`; `jitter` randomises each delay to spread out retries from
+/// many clients. Durations accept the [`HumanDuration`] syntax.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use zalo_types::RetryConfig;
+///
+/// let retry = RetryConfig::default();
+/// assert_eq!(retry.max_retries(), 3);
+/// assert_eq!(retry.base_delay(), Duration::from_millis(200));
+/// assert!(retry.validate().is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    max_retries: u32,
+    base_delay: HumanDuration,
+    max_delay: HumanDuration,
+    jitter: bool,
+}
+
+impl RetryConfig {
+    /// Returns a copy of the policy with a different number of retries.
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Returns a copy of the policy with different delay bounds.
+    #[must_use]
+    pub fn with_delays(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay.into();
+        self.max_delay = max_delay.into();
+        self
+    }
+
+    /// Returns a copy of the policy with jitter toggled.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns how many times a failed call is retried.
+    #[must_use]
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the delay before the first retry.
+    #[must_use]
+    pub fn base_delay(&self) -> Duration {
+        self.base_delay.as_duration()
+    }
+
+    /// Returns the longest delay between two attempts.
+    #[must_use]
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay.as_duration()
+    }
+
+    /// Returns whether delays are randomised.
+    #[must_use]
+    pub fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Checks that the delays are ordered and the retry count is bounded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] when `max_retries` exceeds
+    /// [`MAX_RETRIES_LIMIT`] or `max_delay` is shorter than `base_delay`.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_retries > MAX_RETRIES_LIMIT {
+            return Err(ConfigError::Invalid {
+                field: "retry.max_retries".to_owned(),
+                reason: format!("must be at most {MAX_RETRIES_LIMIT}"),
+            });
+        }
+        if self.max_delay < self.base_delay {
+            return Err(ConfigError::Invalid {
+                field: "retry.max_delay".to_owned(),
+                reason: "must not be shorter than `retry.base_delay`".to_owned(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
-impl Default for LogFormat {
+impl Default for RetryConfig {
     fn default() -> Self {
-        Self::Text
+        Self {
+            max_retries: 3,
+            base_delay: HumanDuration::new(Duration::from_millis(200)),
+            max_delay: HumanDuration::new(Duration::from_secs(10)),
+            jitter: true,
+        }
     }
 }
 
-/// Loads configuration from environment variables and optional TOML files.
+/// Webhook verification settings.
 ///
-/// The loader honours an environment variable named `{prefix}CONFIG_PATH`
-/// (for example `ZALO_BOT_CONFIG_PATH`) which, when set, overrides any file
-/// path configured via [`with_file_path`](Self::with_file_path).
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+/// A single `secret` covers the common case, and `secret_base64` holds a
+/// single binary secret encoded as standard base64. During rotation, `secrets`
+/// lists every accepted secret with the primary (signing) secret first; it may
+/// be given as a TOML array, a comma-separated string or indexed environment
+/// variables such as `ZALO_BOT_WEBHOOK__SECRETS__0`. Setting more than one of
+/// the three fields is rejected by [`AppConfig::validate`].
+///
+/// # Examples
+///
+/// ```
+/// use zalo_types::WebhookConfig;
+///
+/// let webhook = WebhookConfig::default().with_secrets(["current", "previous"]);
+/// assert_eq!(webhook.resolved_secrets(), ["current", "previous"]);
+/// ```
+#[derive(Clone, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default)]
-pub struct ConfigLoader {
-    env_prefix: String,
-    file_path: Option<PathBuf>,
+pub struct WebhookConfig {
+    secret: Option<String>,
+    #[serde(deserialize_with = "deserialize_secret_list")]
+    secrets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret_base64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tolerance: Option<HumanDuration>,
 }
 
-impl ConfigLoader {
-    /// Creates a new loader configured with the provided prefix.
+impl WebhookConfig {
+    /// Sets the single shared webhook secret.
+    #[must_use]
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Sets the rotation list of accepted secrets, primary first.
+    #[must_use]
+    pub fn with_secrets<I, S>(mut self, secrets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.secrets = secrets.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets a single binary secret encoded as standard base64.
+    #[must_use]
+    pub fn with_secret_base64(mut self, secret: impl Into<String>) -> Self {
+        self.secret_base64 = Some(secret.into());
+        self
+    }
+
+    /// Overrides the environment-specific webhook timestamp tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = Some(tolerance.into());
+        self
+    }
+
+    /// Returns the explicitly configured timestamp tolerance, if any.
     ///
-    /// # Examples
+    /// See [`AppConfig::effective_webhook_tolerance`] for the value that
+    /// applies when none is set.
+    #[must_use]
+    pub fn tolerance(&self) -> Option<Duration> {
+        self.tolerance.map(HumanDuration::as_duration)
+    }
+
+    /// Returns the single shared secret, if configured.
+    #[must_use]
+    pub fn secret(&self) -> Option<&str> {
+        self.secret.as_deref()
+    }
+
+    /// Returns the configured rotation list.
+    #[must_use]
+    pub fn secrets(&self) -> &[String] {
+        &self.secrets
+    }
+
+    /// Returns the base64-encoded secret, if configured.
     ///
-    /// ```
-    /// use zalo_types::ConfigLoader;
+    /// The value is not part of [`resolved_secrets`](Self::resolved_secrets)
+    /// because it has to be decoded first.
+    #[must_use]
+    pub fn secret_base64(&self) -> Option<&str> {
+        self.secret_base64.as_deref()
+    }
+
+    /// Returns `true` when any secret field is configured.
+    #[must_use]
+    pub fn has_secret(&self) -> bool {
+        self.secret.is_some() || !self.secrets.is_empty() || self.secret_base64.is_some()
+    }
+
+    /// Returns every accepted secret with the primary secret first.
     ///
-    /// let loader = ConfigLoader::new("ZALO_BOT_");
-    /// assert!(loader.load().is_ok());
-    /// ```
+    /// The single `secret` wins when it is set; callers are expected to run
+    /// [`validate`](Self::validate) beforehand to reject ambiguous setups.
     #[must_use]
-    pub fn new(prefix: impl Into<String>) -> Self {
+    pub fn resolved_secrets(&self) -> Vec<&str> {
+        match &self.secret {
+            Some(secret) => vec![secret.as_str()],
+            None => self.secrets.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Returns a copy of the block with every secret removed.
+    #[must_use]
+    pub fn redacted(&self) -> Self {
         Self {
-            env_prefix: prefix.into(),
-            file_path: None,
+            secret: None,
+            secrets: Vec::new(),
+            secret_base64: None,
+            tolerance: self.tolerance,
         }
     }
 
-    /// Overrides the configuration file path.
+    /// Checks that at most one of `secret`, `secrets` and `secret_base64`
+    /// is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] naming both keys when two of the
+    /// secret fields are configured.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        check_exclusive(&[
+            ("webhook.secret", self.secret.is_some()),
+            ("webhook.secrets", !self.secrets.is_empty()),
+            ("webhook.secret_base64", self.secret_base64.is_some()),
+        ])
+    }
+}
+
+impl fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("secret", &self.secret.as_ref().map(|_| REDACTED))
+            .field("secrets", &vec![REDACTED; self.secrets.len()])
+            .field(
+                "secret_base64",
+                &self.secret_base64.as_ref().map(|_| REDACTED),
+            )
+            .field("tolerance", &self.tolerance)
+            .finish()
+    }
+}
+
+/// Rejects mutually exclusive options when more than one of them is set.
+///
+/// `options` pairs each key with whether it is set; the error is reported on
+/// the second key set and names the first one.
+fn check_exclusive(options: &[(&str, bool)]) -> Result<(), ConfigError> {
+    let mut set = options
+        .iter()
+        .filter(|(_, is_set)| *is_set)
+        .map(|(key, _)| *key);
+    if let (Some(first), Some(second)) = (set.next(), set.next()) {
+        return Err(ConfigError::Invalid {
+            field: second.to_owned(),
+            reason: format!("cannot be combined with `{first}`"),
+        });
+    }
+
+    Ok(())
+}
+
+/// Built-in migrations for keys that are still honoured but have been
+/// renamed, applied before any registered with
+/// [`ConfigLoader::with_migration`].
+const BUILTIN_MIGRATIONS: &[ConfigMigration] = &[
+    ConfigMigration::rename("log_level", "logging.filter"),
+    ConfigMigration::rename("log_format", "logging.format"),
+];
+
+/// Rename of a configuration key, optionally converting its value, applied to
+/// the merged sources before extraction.
+///
+/// A migration copies the value of the deprecated `from` key to `to` unless
+/// `to` is set explicitly by a file, JSON or environment layer, and reports a
+/// [`ConfigWarning::DeprecatedKey`]. Keys are dotted paths such as
+/// `logging.filter`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use zalo_types::ConfigMigration;
+///
+/// let migration = ConfigMigration::rename("log_level", "logging.filter");
+/// let migrated = migration.migrate(json!({ "log_level": "debug" }))?;
+/// assert_eq!(migrated["logging"]["filter"], "debug");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConfigMigration {
+    from: &'static str,
+    to: &'static str,
+    transform: fn(JsonValue) -> JsonValue,
+}
+
+impl ConfigMigration {
+    /// Creates a migration moving the value of `from` to `to` unchanged.
+    #[must_use]
+    pub const fn rename(from: &'static str, to: &'static str) -> Self {
+        Self::with_transform(from, to, keep_value)
+    }
+
+    /// Creates a migration moving the value of `from` to `to` after passing
+    /// it through `transform`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::path::Path;
-    /// use zalo_types::ConfigLoader;
+    /// use serde_json::{json, Value};
+    /// use zalo_types::ConfigMigration;
     ///
-    /// let loader = ConfigLoader::new("ZALO_").with_file_path(Path::new("config.toml"));
-    /// assert_eq!(loader.file_path().unwrap(), Path::new("config.toml"));
+    /// fn seconds(value: Value) -> Value {
+    ///     value.as_u64().map_or(value, |secs| format!("{secs}s").into())
+    /// }
+    ///
+    /// let migration = ConfigMigration::with_transform("grace_secs", "shutdown_grace_period", seconds);
+    /// let migrated = migration.migrate(json!({ "grace_secs": 45 }))?;
+    /// assert_eq!(migrated["shutdown_grace_period"], "45s");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// ```
     #[must_use]
-    pub fn with_file_path(mut self, path: impl AsRef<Path>) -> Self {
-        self.file_path = Some(path.as_ref().to_path_buf());
-        self
+    pub const fn with_transform(
+        from: &'static str,
+        to: &'static str,
+        transform: fn(JsonValue) -> JsonValue,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            transform,
+        }
+    }
+
+    /// Returns the deprecated key.
+    #[must_use]
+    pub fn from_key(&self) -> &'static str {
+        self.from
+    }
+
+    /// Returns the key that replaces it.
+    #[must_use]
+    pub fn to_key(&self) -> &'static str {
+        self.to
+    }
+
+    /// Applies the migration to a standalone configuration document.
+    ///
+    /// Any `to` key already present in `document` is kept. This runs the same
+    /// logic as [`ConfigLoader`], so migrations can be tested in isolation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Extraction`] when `document` is not an object.
+    pub fn migrate(&self, document: JsonValue) -> TypesResult<JsonValue> {
+        let figment = Figment::from(Serialized::defaults(document));
+        let (figment, _) = self.apply(figment, "").map_err(TypesError::from)?;
+
+        figment
+            .extract()
+            .map_err(ConfigError::from)
+            .map_err(TypesError::from)
+    }
+
+    /// Merges the migrated value into `figment`, returning whether `from` was
+    /// present. A `to` key set by a layer other than `defaults_name` wins.
+    fn apply(&self, figment: Figment, defaults_name: &str) -> Result<(Figment, bool), ConfigError> {
+        let Ok(value) = figment.find_value(self.from) else {
+            return Ok((figment, false));
+        };
+        let overridden = figment
+            .find_metadata(self.to)
+            .is_some_and(|metadata| metadata.name != defaults_name);
+        if overridden {
+            return Ok((figment, true));
+        }
+
+        let value = (self.transform)(value.deserialize()?);
+        Ok((figment.merge(Serialized::default(self.to, value)), true))
+    }
+}
+
+impl PartialEq for ConfigMigration {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from
+            && self.to == other.to
+            && std::ptr::fn_addr_eq(self.transform, other.transform)
+    }
+}
+
+impl Eq for ConfigMigration {}
+
+fn keep_value(value: JsonValue) -> JsonValue {
+    value
+}
+
+/// Key of the `{prefix}CONFIG_PATH` variable read by the loader itself.
+const CONFIG_PATH_ENV_KEY: &str = "config_path";
+
+/// Every configuration field that an environment variable may set, in the
+/// dotted form produced by splitting on `__`. Deprecated keys are covered by
+/// the migrations instead.
+const KNOWN_ENV_KEYS: &[&str] = &[
+    "environment",
+    "strict",
+    "shutdown_grace_period",
+    "logging.filter",
+    "logging.format",
+    "logging.output",
+    "logging.directory",
+    "logging.span_timing",
+    "logging.production_max_level",
+    "webhook.secret",
+    "webhook.secrets",
+    "webhook.secret_base64",
+    "webhook.tolerance",
+    "retry.max_retries",
+    "retry.base_delay",
+    "retry.max_delay",
+    "retry.jitter",
+];
+
+/// Largest edit distance at which a known key is suggested for a typo.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// Reports every variable under `prefix` that does not map to a known key,
+/// suggesting the closest known variable. Keys read by `migrations` count as
+/// known.
+fn unknown_env_keys<'a>(
+    prefix: &str,
+    migrations: impl Iterator<Item = &'a ConfigMigration> + Clone,
+) -> Vec<ConfigWarning> {
+    let mut warnings: Vec<ConfigWarning> = env::vars_os()
+        .filter_map(|(name, _)| name.into_string().ok())
+        .filter_map(|variable| {
+            let key = variable
+                .strip_prefix(prefix)?
+                .to_ascii_lowercase()
+                .replace("__", ".");
+            let known = key == CONFIG_PATH_ENV_KEY
+                || KNOWN_ENV_KEYS.contains(&key.as_str())
+                || key.starts_with("webhook.secrets.")
+                || migrations.clone().any(|migration| migration.from == key);
+            (!known).then(|| ConfigWarning::UnknownEnvKey {
+                suggestion: nearest_env_key(&key).map(|known| env_variable(prefix, known)),
+                variable,
+            })
+        })
+        .collect();
+    warnings.sort_by_key(ToString::to_string);
+    warnings
+}
+
+/// Returns the known key closest to `key` once separators are ignored.
+fn nearest_env_key(key: &str) -> Option<&'static str> {
+    let flat = key.replace('.', "_");
+    KNOWN_ENV_KEYS
+        .iter()
+        .chain([&CONFIG_PATH_ENV_KEY])
+        .map(|known| (edit_distance(&flat, &known.replace('.', "_")), *known))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Returns the environment variable setting the dotted `key`.
+fn env_variable(prefix: &str, key: &str) -> String {
+    format!("{prefix}{}", key.to_ascii_uppercase().replace('.', "__"))
+}
+
+/// Levenshtein distance between two ASCII strings.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right = right.as_bytes();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_byte) in left.bytes().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+        for (j, right_byte) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_byte != *right_byte);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+fn deserialize_secret_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct SecretListVisitor;
+
+    impl<'de> Visitor<'de> for SecretListVisitor {
+        type Value = Vec<String>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a list of secrets, a comma-separated string or an indexed table")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(normalize_secrets(value.split(',')))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut secrets = Vec::new();
+            while let Some(secret) = seq.next_element::<String>()? {
+                secrets.push(secret);
+            }
+            Ok(normalize_secrets(secrets))
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut indexed = Vec::new();
+            while let Some((key, secret)) = map.next_entry::<String, String>()? {
+                let index = key.parse::<usize>().map_err(|_| {
+                    de::Error::invalid_value(Unexpected::Str(&key), &"a numeric index")
+                })?;
+                indexed.push((index, secret));
+            }
+            indexed.sort_by_key(|(index, _)| *index);
+            Ok(normalize_secrets(
+                indexed.into_iter().map(|(_, secret)| secret),
+            ))
+        }
+    }
+
+    deserializer.deserialize_any(SecretListVisitor)
+}
+
+fn normalize_secrets<I, S>(secrets: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    secrets
+        .into_iter()
+        .map(|secret| secret.as_ref().trim().to_owned())
+        .filter(|secret| !secret.is_empty())
+        .collect()
+}
+
+/// Non-fatal configuration issue reported by
+/// [`ConfigLoader::load_with_warnings`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigWarning {
+    /// A renamed key was used; its value was applied to the replacement.
+    DeprecatedKey {
+        /// Deprecated key found in the sources.
+        key: &'static str,
+        /// Key that replaces it.
+        replacement: &'static str,
+    },
+    /// The global log filter is `debug` or more verbose in production.
+    DebugLoggingInProduction {
+        /// Effective filter expression.
+        filter: String,
+    },
+    /// An environment variable carries the loader's prefix but sets no known
+    /// key, typically a nested key written with `_` instead of `__`.
+    UnknownEnvKey {
+        /// Name of the ignored variable.
+        variable: String,
+        /// Closest valid variable, if any is close enough.
+        suggestion: Option<String>,
+    },
+}
+
+impl ConfigWarning {
+    /// Emits the warning as a `WARN` tracing event.
+    fn log(&self) {
+        match self {
+            ConfigWarning::DeprecatedKey { key, replacement } => warn!(
+                deprecated = key,
+                replacement, "deprecated configuration key; rename it to the replacement"
+            ),
+            ConfigWarning::DebugLoggingInProduction { filter } => {
+                warn!(
+                    filter = filter.as_str(),
+                    "debug logging enabled in production"
+                );
+            }
+            ConfigWarning::UnknownEnvKey {
+                variable,
+                suggestion,
+            } => warn!(
+                variable = variable.as_str(),
+                suggestion = suggestion.as_deref(),
+                "environment variable does not set any configuration key"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigWarning::DeprecatedKey { key, replacement } => {
+                write!(f, "`{key}` is deprecated, use `{replacement}` instead")
+            }
+            ConfigWarning::DebugLoggingInProduction { filter } => {
+                write!(
+                    f,
+                    "log filter `{filter}` enables debug logging in production"
+                )
+            }
+            ConfigWarning::UnknownEnvKey {
+                variable,
+                suggestion,
+            } => {
+                write!(
+                    f,
+                    "environment variable `{variable}` does not set any configuration key"
+                )?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean `{suggestion}`?"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Immutable configuration snapshot shared between tasks.
+///
+/// Cloning a `SharedConfig` only bumps a reference count; see
+/// [`ConfigLoader::load_shared`].
+pub type SharedConfig = Arc<AppConfig>;
+
+/// How [`ConfigLoader`] treats its configured file.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_types::{ConfigLoader, FileRequirement};
+///
+/// let loader = ConfigLoader::new("ZALO_").with_file_optional("config.toml");
+/// assert_eq!(loader.file_requirement(), FileRequirement::Optional);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileRequirement {
+    /// The file must exist, but `{prefix}CONFIG_PATH` may point elsewhere
+    /// (default).
+    #[default]
+    Overridable,
+    /// A missing file is skipped; a file named by `{prefix}CONFIG_PATH` must
+    /// still exist.
+    Optional,
+    /// The file must exist and `{prefix}CONFIG_PATH` cannot redirect the
+    /// loader.
+    Required,
+}
+
+/// Loads configuration from environment variables and optional TOML files.
+///
+/// The loader honours an environment variable named `{prefix}CONFIG_PATH`
+/// (for example `ZALO_BOT_CONFIG_PATH`) which, when set, overrides any file
+/// path configured via [`with_file_path`](Self::with_file_path). Use
+/// [`with_required_file`](Self::with_required_file) to forbid that override.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigLoader {
+    env_prefix: String,
+    file_path: Option<PathBuf>,
+    file_requirement: FileRequirement,
+    defaults: AppConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_value: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    prefix_fallbacks: Vec<String>,
+    strict_env: bool,
+    #[serde(skip)]
+    migrations: Vec<ConfigMigration>,
+}
+
+impl ConfigLoader {
+    /// Creates a new loader configured with the provided prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::new("ZALO_BOT_");
+    /// assert!(loader.load().is_ok());
+    /// ```
+    #[must_use]
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            env_prefix: prefix.into(),
+            file_path: None,
+            file_requirement: FileRequirement::Overridable,
+            defaults: AppConfig::default(),
+            json_value: None,
+            prefix_fallbacks: Vec::new(),
+            strict_env: false,
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Creates a loader whose prefix is read from the `var_name` environment
+    /// variable, falling back to `ZALO_BOT_` when it is unset or blank.
+    ///
+    /// This lets one binary run several isolated instances, each selecting
+    /// its own variables with, for example, `CONFIG_PREFIX=TENANT_A_`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] when the variable is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::from_env_prefix_var("ZALO_DOC_UNSET_PREFIX")?;
+    /// assert_eq!(loader.env_prefix(), "ZALO_BOT_");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_env_prefix_var(var_name: &str) -> TypesResult<Self> {
+        match env::var(var_name) {
+            Ok(prefix) if !prefix.trim().is_empty() => Ok(Self::new(prefix.trim())),
+            Ok(_) | Err(VarError::NotPresent) => Ok(Self::default()),
+            Err(VarError::NotUnicode(_)) => Err(ConfigError::Invalid {
+                field: var_name.to_owned(),
+                reason: "value is not valid UTF-8".to_owned(),
+            }
+            .into()),
+        }
+    }
+
+    /// Returns the prefix of the environment variables read by the loader.
+    #[must_use]
+    pub fn env_prefix(&self) -> &str {
+        &self.env_prefix
+    }
+
+    /// Overrides the configuration file path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::new("ZALO_").with_file_path(Path::new("config.toml"));
+    /// assert_eq!(loader.file_path().unwrap(), Path::new("config.toml"));
+    /// ```
+    #[must_use]
+    pub fn with_file_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.file_path = Some(path.as_ref().to_path_buf());
+        if self.file_requirement == FileRequirement::Optional {
+            self.file_requirement = FileRequirement::Overridable;
+        }
+        self
+    }
+
+    /// Uses the provided file when it exists and skips it otherwise.
+    ///
+    /// Unlike [`with_file_path`](Self::with_file_path), a missing file is not
+    /// an error: the configuration is then built from the defaults and the
+    /// environment alone. A file named by `{prefix}CONFIG_PATH` must still
+    /// exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::new("ZALO_DOC_OPTIONAL_").with_file_optional("missing.toml");
+    /// assert!(loader.is_file_optional());
+    /// assert!(loader.load().is_ok());
+    /// ```
+    #[must_use]
+    pub fn with_file_optional(mut self, path: impl AsRef<Path>) -> Self {
+        self.file_path = Some(path.as_ref().to_path_buf());
+        self.file_requirement = FileRequirement::Optional;
+        self
+    }
+
+    /// Returns how the configured file is treated.
+    #[must_use]
+    pub fn file_requirement(&self) -> FileRequirement {
+        self.file_requirement
+    }
+
+    /// Returns `true` when a missing configured file is skipped.
+    #[must_use]
+    pub fn is_file_optional(&self) -> bool {
+        self.file_requirement == FileRequirement::Optional
+    }
+
+    /// Requires the configuration to be loaded from the provided file.
+    ///
+    /// Unlike [`with_file_path`](Self::with_file_path), the `{prefix}CONFIG_PATH`
+    /// variable cannot redirect the loader to another file: [`load`](Self::load)
+    /// fails when the variable points elsewhere or when the file is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::new("ZALO_").with_required_file("config.toml");
+    /// assert!(loader.is_file_required());
+    /// assert_eq!(loader.file_path().unwrap(), Path::new("config.toml"));
+    /// ```
+    #[must_use]
+    pub fn with_required_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.file_path = Some(path.as_ref().to_path_buf());
+        self.file_requirement = FileRequirement::Required;
+        self
+    }
+
+    /// Returns `true` when the configured file must be used.
+    #[must_use]
+    pub fn is_file_required(&self) -> bool {
+        self.file_requirement == FileRequirement::Required
+    }
+
+    /// Replaces the baseline configuration that file and environment layers
+    /// are merged on top of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, ConfigLoader, Environment};
+    ///
+    /// let baseline = AppConfig::default().with_environment(Environment::Staging);
+    /// let loader = ConfigLoader::new("ZALO_").with_defaults(baseline.clone());
+    /// assert_eq!(loader.defaults(), &baseline);
+    /// ```
+    #[must_use]
+    pub fn with_defaults(mut self, config: AppConfig) -> Self {
+        self.defaults = config;
+        self
+    }
+
+    /// Adds a JSON value merged on top of the defaults and the file but below
+    /// environment variables.
+    ///
+    /// The value must be an object shaped like [`AppConfig`]; other shapes
+    /// are reported by [`load`](Self::load). Calling this again replaces the
+    /// previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zalo_types::{ConfigLoader, Environment};
+    ///
+    /// let loader = ConfigLoader::new("ZALO_DOC_JSON_")
+    ///     .with_json_value(json!({ "environment": "staging" }));
+    /// assert_eq!(loader.load()?.environment(), Environment::Staging);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_json_value(mut self, value: JsonValue) -> Self {
+        self.json_value = Some(value);
+        self
+    }
+
+    /// Sets additional environment prefixes consulted when the loader's own
+    /// prefix does not define a value.
+    ///
+    /// Prefixes are listed in priority order: a variable under an earlier
+    /// prefix wins over the same variable under a later one, and the loader's
+    /// own prefix always wins. A prefix equal to the loader's own is ignored,
+    /// so the full migration chain can be passed as is. `{prefix}CONFIG_PATH`
+    /// is only read from the loader's own prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::new("ZALO_BOT_").with_prefix_fallbacks(["ZALO_BOT_", "ZALO_"]);
+    /// assert_eq!(loader.prefix_fallbacks(), ["ZALO_"]);
+    /// ```
+    #[must_use]
+    pub fn with_prefix_fallbacks<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.prefix_fallbacks = prefixes
+            .into_iter()
+            .map(Into::into)
+            .filter(|prefix| *prefix != self.env_prefix)
+            .collect();
+        self
+    }
+
+    /// Reports environment variables under the loader's prefix that do not
+    /// set any known key.
+    ///
+    /// Figment silently ignores such variables, so a nested key written with
+    /// a single underscore (`ZALO_BOT_LOGGING_FILTER` instead of
+    /// `ZALO_BOT_LOGGING__FILTER`) would otherwise leave the default in
+    /// place. Each one becomes a [`ConfigWarning::UnknownEnvKey`] naming the
+    /// closest valid variable. Fallback prefixes are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let loader = ConfigLoader::new("ZALO_DOC_STRICT_ENV_").with_strict_env(true);
+    /// let (_, warnings) = loader.load_with_warnings()?;
+    /// assert!(warnings.is_empty());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_strict_env(mut self, strict_env: bool) -> Self {
+        self.strict_env = strict_env;
+        self
+    }
+
+    /// Returns `true` when unknown environment variables are reported.
+    #[must_use]
+    pub fn is_strict_env(&self) -> bool {
+        self.strict_env
+    }
+
+    /// Registers a migration applied to the merged sources before extraction.
+    ///
+    /// Migrations run in registration order, after the built-in renames of
+    /// `log_level` and `log_format`, so a later migration sees the keys an
+    /// earlier one produced. Migrations are not serialised with the loader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zalo_types::{ConfigLoader, ConfigMigration};
+    ///
+    /// let config = ConfigLoader::new("ZALO_DOC_MIGRATION_")
+    ///     .with_json_value(json!({ "filter": "warn" }))
+    ///     .with_migration(ConfigMigration::rename("filter", "logging.filter"))
+    ///     .load()?;
+    /// assert_eq!(config.logging().filter(), "warn");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_migration(mut self, migration: ConfigMigration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Returns the registered migrations in application order, excluding the
+    /// built-in ones.
+    #[must_use]
+    pub fn migrations(&self) -> &[ConfigMigration] {
+        &self.migrations
+    }
+
+    /// Returns the fallback environment prefixes in priority order.
+    #[must_use]
+    pub fn prefix_fallbacks(&self) -> &[String] {
+        &self.prefix_fallbacks
+    }
+
+    /// Returns the baseline configuration used by [`load`](Self::load).
+    #[must_use]
+    pub fn defaults(&self) -> &AppConfig {
+        &self.defaults
+    }
+
+    /// Returns the configured file path, if any.
+    #[must_use]
+    pub fn file_path(&self) -> Option<&Path> {
+        self.file_path.as_deref()
+    }
+
+    /// Loads the configuration from the configured sources.
+    ///
+    /// Each call runs inside a `config.load` tracing span recording the
+    /// resolved file `path` (if any) and the resulting `environment`.
+    ///
+    /// Environment variables take precedence over file values and defaults.
+    /// Defaults come from [`with_defaults`](Self::with_defaults) and fall back
+    /// to [`AppConfig::default`].
+    ///
+    /// Deprecated keys such as `log_level` are still applied to the key that
+    /// replaced them, unless a source sets the replacement explicitly; each
+    /// one found logs a warning naming the replacement.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TypesError::Config`] when the configuration file is missing,
+    /// when a required file is overridden by `{prefix}CONFIG_PATH`, when that
+    /// variable is not valid UTF-8 or when the model fails validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let result = ConfigLoader::default().load();
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn load(&self) -> TypesResult<AppConfig> {
+        self.load_layers(true).map(|(config, _)| config)
+    }
+
+    /// Loads the configuration like [`load`](Self::load) and also returns the
+    /// non-fatal issues found on the way.
+    ///
+    /// The warnings are returned instead of logged, so the caller decides
+    /// whether to log, report or ignore them. Besides deprecated keys, a
+    /// `debug` (or more verbose) global filter in
+    /// [`Environment::Production`] is reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let (config, warnings) = ConfigLoader::new("ZALO_DOC_WARNINGS_").load_with_warnings()?;
+    /// for warning in &warnings {
+    ///     eprintln!("config warning: {warning}");
+    /// }
+    /// assert!(warnings.is_empty());
+    /// # let _ = config;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_with_warnings(&self) -> TypesResult<(AppConfig, Vec<ConfigWarning>)> {
+        let (config, mut warnings) = self.load_layers(false)?;
+        if config.environment() == Environment::Production && config.is_debug_logging() {
+            warnings.push(ConfigWarning::DebugLoggingInProduction {
+                filter: config.effective_filter(),
+            });
+        }
+
+        Ok((config, warnings))
+    }
+
+    /// Loads the prospective configuration and lists how it differs from
+    /// `current`, without logging warnings or applying anything.
+    ///
+    /// This lets operators review a pending rollout before triggering a
+    /// reload; see [`AppConfig::diff`] for how changes are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load) and
+    /// [`AppConfig::diff`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::{AppConfig, ConfigLoader};
+    ///
+    /// let changes = ConfigLoader::new("ZALO_DOC_PREVIEW_").preview_changes(&AppConfig::default())?;
+    /// for change in &changes {
+    ///     println!("pending: {change}");
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn preview_changes(&self, current: &AppConfig) -> TypesResult<Vec<ConfigChange>> {
+        let (prospective, _) = self.load_layers(false)?;
+        current.diff(&prospective)
+    }
+
+    /// Merges every source, extracts and validates the model, logging the
+    /// collected warnings when `log_warnings` is set.
+    fn load_layers(&self, log_warnings: bool) -> TypesResult<(AppConfig, Vec<ConfigWarning>)> {
+        let span = load_span();
+        let _entered = span.enter();
+
+        let file = match self.resolve_file()? {
+            Some(path) => {
+                let contents = read_config_file(&path)?;
+                Some((path, contents))
+            }
+            None => None,
+        };
+        self.extract_layers(&span, file, log_warnings)
+    }
+
+    /// Returns the configuration file to read, if any, after applying the
+    /// path override and checking that the file exists.
+    fn resolve_file(&self) -> TypesResult<Option<PathBuf>> {
+        let env_path = env_config_path(&self.env_prefix)?;
+        if let (FileRequirement::Required, Some(required), Some(actual)) = (
+            self.file_requirement,
+            self.file_path.as_deref(),
+            env_path.as_deref(),
+        ) {
+            if required != actual {
+                return Err(ConfigError::RequiredFileOverridden {
+                    required: required.to_path_buf(),
+                    actual: actual.to_path_buf(),
+                }
+                .into());
+            }
+        }
+        let optional = self.file_requirement == FileRequirement::Optional && env_path.is_none();
+        let Some(path) = env_path.or_else(|| self.file_path.clone()) else {
+            return Ok(None);
+        };
+        if optional && !path_exists(&path) {
+            return Ok(None);
+        }
+        if !path_exists(&path) {
+            return Err(ConfigError::MissingFile { path }.into());
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Merges every layer on top of the already-read file contents and
+    /// extracts the configuration.
+    fn extract_layers(
+        &self,
+        span: &Span,
+        file: Option<(PathBuf, String)>,
+        log_warnings: bool,
+    ) -> TypesResult<(AppConfig, Vec<ConfigWarning>)> {
+        let defaults = Serialized::defaults(&self.defaults);
+        let defaults_name = defaults.metadata().name;
+        let mut figment = Figment::from(defaults);
+
+        if let Some((path, contents)) = file {
+            span.record("path", field::display(path.display()));
+            figment = figment.merge(TomlFile { path, contents });
+        }
+
+        if let Some(value) = &self.json_value {
+            figment = figment.merge(Serialized::defaults(value));
+        }
+
+        // Lowest priority first, so that earlier prefixes override later ones.
+        for prefix in self.prefix_fallbacks.iter().rev() {
+            figment = figment.merge(Env::prefixed(prefix).split("__"));
+        }
+        figment = figment.merge(Env::prefixed(&self.env_prefix).split("__"));
+        let (figment, mut warnings) = self.apply_migrations(figment, &defaults_name)?;
+        if self.strict_env {
+            warnings.extend(unknown_env_keys(
+                &self.env_prefix,
+                BUILTIN_MIGRATIONS.iter().chain(&self.migrations),
+            ));
+        }
+        if log_warnings {
+            for warning in &warnings {
+                warning.log();
+            }
+        }
+
+        let config = figment
+            .extract::<AppConfig>()
+            .map_err(ConfigError::from)
+            .map_err(TypesError::from)?;
+        config.validate()?;
+        span.record("environment", config.environment().as_str());
+
+        Ok((config, warnings))
+    }
+
+    /// Applies the built-in migrations, then the registered ones, reporting
+    /// each deprecated key found in the sources.
+    fn apply_migrations(
+        &self,
+        mut figment: Figment,
+        defaults_name: &str,
+    ) -> Result<(Figment, Vec<ConfigWarning>), ConfigError> {
+        let mut warnings = Vec::new();
+        for migration in BUILTIN_MIGRATIONS.iter().chain(&self.migrations) {
+            let (migrated, found) = migration.apply(figment, defaults_name)?;
+            figment = migrated;
+            if found {
+                warnings.push(ConfigWarning::DeprecatedKey {
+                    key: migration.from,
+                    replacement: migration.to,
+                });
+            }
+        }
+
+        Ok((figment, warnings))
+    }
+
+    /// Loads the configuration into a [`SharedConfig`] that can be handed to
+    /// many tasks without deep-cloning it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// let shared = ConfigLoader::new("ZALO_DOC_SHARED_").load_shared()?;
+    /// let for_worker = Arc::clone(&shared);
+    /// assert!(Arc::ptr_eq(&shared, &for_worker));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_shared(&self) -> TypesResult<SharedConfig> {
+        self.load().map(Arc::new)
+    }
+
+    /// Loads the configuration without blocking the async runtime.
+    ///
+    /// The file is read with [`tokio::fs`] and the layers are merged and
+    /// extracted on the blocking thread pool. The result is the same as
+    /// [`load`](Self::load), which remains the choice outside async contexts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load), or
+    /// [`TypesError::Other`] when the runtime shuts down before extraction
+    /// completes.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic if extraction panics on the blocking thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zalo_types::ConfigLoader;
+    ///
+    /// # async fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ConfigLoader::new("ZALO_DOC_ASYNC_").load_async().await?;
+    /// assert!(!config.is_strict());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn load_async(&self) -> TypesResult<AppConfig> {
+        let span = load_span();
+
+        let file = match span.in_scope(|| self.resolve_file())? {
+            Some(path) => {
+                let contents =
+                    tokio::fs::read_to_string(&path)
+                        .await
+                        .map_err(|source| ConfigError::Read {
+                            path: path.clone(),
+                            source,
+                        })?;
+                Some((path, normalize_source(&contents)))
+            }
+            None => None,
+        };
+
+        let loader = self.clone();
+        let task = tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            loader.extract_layers(&span, file, true)
+        });
+        match task.await {
+            Ok(result) => result.map(|(config, _)| config),
+            Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+            Err(error) => Err(TypesError::with_message(
+                "configuration load was cancelled before completing",
+            )
+            .with_source(error)),
+        }
+    }
+}
+
+/// Creates the span covering one configuration load.
+fn load_span() -> Span {
+    info_span!(
+        "config.load",
+        path = field::Empty,
+        environment = field::Empty
+    )
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENV_PREFIX)
+    }
+}
+
+fn path_exists(path: &Path) -> bool {
+    path.exists()
+}
+
+/// Reads the configuration file, stripping a leading UTF-8 BOM and converting
+/// CRLF line endings so Windows-authored files parse cleanly.
+fn read_config_file(path: &Path) -> Result<String, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    Ok(normalize_source(&contents))
+}
+
+/// TOML provider over the normalised contents of a configuration file.
+///
+/// Reports the file as its source, like [`Toml::file`], so extraction errors
+/// and value metadata name the path.
+struct TomlFile {
+    path: PathBuf,
+    contents: String,
+}
+
+impl Provider for TomlFile {
+    fn metadata(&self) -> Metadata {
+        Metadata::from("TOML file", self.path.as_path())
+    }
+
+    fn data(&self) -> Result<figment::value::Map<Profile, Dict>, figment::Error> {
+        Toml::string(&self.contents).data()
+    }
+}
+
+fn normalize_source(contents: &str) -> String {
+    contents
+        .strip_prefix('\u{feff}')
+        .unwrap_or(contents)
+        .replace("\r\n", "\n")
+}
+
+fn env_config_path(prefix: &str) -> Result<Option<PathBuf>, ConfigError> {
+    let mut key = String::with_capacity(prefix.len() + "CONFIG_PATH".len());
+    key.push_str(prefix);
+    key.push_str("CONFIG_PATH");
+
+    let value = match env::var(&key) {
+        Ok(value) => value,
+        Err(VarError::NotPresent) => return Ok(None),
+        Err(VarError::NotUnicode(_)) => {
+            return Err(ConfigError::Invalid {
+                field: key,
+                reason: "value is not valid UTF-8".to_owned(),
+            })
+        }
+    };
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(PathBuf::from(trimmed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::write;
+    use std::sync::{Arc, Mutex};
+
+    use tempfile::NamedTempFile;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::{Layer, Registry};
+
+    static ENV_GUARD: Mutex<()> = Mutex::new(());
+
+    /// Captures the fields recorded on `config.load` spans and on the events
+    /// emitted inside them.
+    #[derive(Clone, Default)]
+    struct LoadSpanFields(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl LoadSpanFields {
+        fn get(&self, name: &str) -> Option<String> {
+            let fields = self.0.lock().expect("lock poisoned");
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    impl Visit for LoadSpanFields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            let mut fields = self.0.lock().expect("lock poisoned");
+            fields.push((field.name().to_owned(), value.to_owned()));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            let mut fields = self.0.lock().expect("lock poisoned");
+            fields.push((field.name().to_owned(), format!("{value:?}")));
+        }
+    }
+
+    impl<S> Layer<S> for LoadSpanFields
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            if attrs.metadata().name() == "config.load" {
+                attrs.record(&mut self.clone());
+            }
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+            if ctx
+                .span(id)
+                .is_some_and(|span| span.name() == "config.load")
+            {
+                values.record(&mut self.clone());
+            }
+        }
+
+        fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+            if ctx
+                .event_span(event)
+                .is_some_and(|span| span.name() == "config.load")
+            {
+                event.record(&mut self.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn loads_default_configuration() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
+        std::env::remove_var("ZALO_BOT_LOGGING__FILTER");
+        std::env::remove_var("ZALO_BOT_LOGGING__FORMAT");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+
+        let config = ConfigLoader::default()
+            .load()
+            .expect("default configuration should load");
+
+        assert_eq!(config.environment(), Environment::Development);
+        assert_eq!(config.logging().filter(), "info");
+        assert_eq!(config.logging().format(), LogFormat::Text);
+    }
+
+    #[test]
+    fn merges_environment_variables() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::set_var("ZALO_BOT_ENVIRONMENT", "production");
+        std::env::set_var("ZALO_BOT_LOGGING__FILTER", "debug");
+        std::env::set_var("ZALO_BOT_LOGGING__FORMAT", "json");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+
+        let config = ConfigLoader::default()
+            .load()
+            .expect("config should respect env overrides");
+
+        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
+        std::env::remove_var("ZALO_BOT_LOGGING__FILTER");
+        std::env::remove_var("ZALO_BOT_LOGGING__FORMAT");
+
+        assert_eq!(config.environment(), Environment::Production);
+        assert_eq!(config.logging().filter(), "debug");
+        assert_eq!(config.logging().format(), LogFormat::Json);
+    }
+
+    #[test]
+    fn parses_log_format_aliases_case_insensitively() {
+        let cases = [
+            ("text", LogFormat::Text),
+            ("Plain", LogFormat::Text),
+            ("HUMAN", LogFormat::Text),
+            ("Json", LogFormat::Json),
+            ("JSON", LogFormat::Json),
+            ("structured", LogFormat::Json),
+            ("Bunyan", LogFormat::Bunyan),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<LogFormat>(), Ok(expected), "input `{input}`");
+        }
+        for format in [LogFormat::Text, LogFormat::Json, LogFormat::Bunyan] {
+            assert_eq!(format.as_str().parse::<LogFormat>(), Ok(format));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_log_format() {
+        let error = "yaml".parse::<LogFormat>().expect_err("unknown format");
+
+        assert_eq!(error.input(), "yaml");
+        assert!(error.to_string().contains("yaml"));
+    }
+
+    #[test]
+    fn loads_split_log_output_from_env() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        std::env::set_var("ZALO_BOT_LOGGING__OUTPUT", "split");
+
+        let config = ConfigLoader::default().load();
+        std::env::remove_var("ZALO_BOT_LOGGING__OUTPUT");
+
+        let config = config.expect("split output should load");
+        assert_eq!(config.logging().output(), LogOutput::Split);
+    }
+
+    #[test]
+    fn effective_filter_uses_explicit_filter() {
+        let logging = LoggingConfig::new("warn,zalo_bot=trace", LogFormat::Json);
+        let config = AppConfig::default().with_logging(logging);
+
+        assert_eq!(config.effective_filter(), "warn,zalo_bot=trace");
+    }
+
+    #[test]
+    fn effective_filter_defaults_to_info() {
+        assert_eq!(AppConfig::default().effective_filter(), "info");
+    }
+
+    #[test]
+    fn effective_filter_applies_production_ceiling() {
+        let logging = LoggingConfig::new("debug,zalo_bot=trace,hyper=warn", LogFormat::Json)
+            .with_production_max_level(LevelFilter::INFO);
+        let config = AppConfig::default().with_logging(logging);
+        assert_eq!(config.effective_filter(), "debug,zalo_bot=trace,hyper=warn");
+
+        let production = config.with_environment(Environment::Production);
+        assert_eq!(
+            production.effective_filter(),
+            "info,zalo_bot=info,hyper=warn"
+        );
+        assert!(!production.is_debug_logging());
+    }
+
+    #[test]
+    fn custom_defaults_form_the_baseline() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
+        std::env::remove_var("ZALO_BOT_LOGGING__FILTER");
+        std::env::remove_var("ZALO_BOT_LOGGING__FORMAT");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        let baseline = AppConfig::default()
+            .with_environment(Environment::Staging)
+            .with_logging(LoggingConfig::new("debug", LogFormat::Json));
+        let loader = ConfigLoader::default().with_defaults(baseline.clone());
+
+        let config = loader.load().expect("baseline should load");
+        assert_eq!(config, baseline);
+
+        std::env::set_var("ZALO_BOT_ENVIRONMENT", "production");
+        let overridden = loader.load().expect("env should override baseline");
+        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
+
+        assert_eq!(overridden.environment(), Environment::Production);
+        assert_eq!(overridden.logging().filter(), "debug");
+    }
+
+    #[test]
+    fn load_records_span_with_path() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), "environment = \"staging\"\n").expect("write config");
+
+        let fields = LoadSpanFields::default();
+        let subscriber = Registry::default().with(fields.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            ConfigLoader::default()
+                .with_file_path(file.path())
+                .load()
+                .expect("file config should load");
+        });
+
+        let expected_path = file.path().display().to_string();
+        assert_eq!(fields.get("path").as_deref(), Some(expected_path.as_str()));
+        assert_eq!(fields.get("environment").as_deref(), Some("staging"));
+    }
+
+    #[test]
+    fn honours_deprecated_keys_with_warning() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let fields = LoadSpanFields::default();
+        let subscriber = Registry::default().with(fields.clone());
+        std::env::set_var("ZALO_DEPRECATED_TEST_LOG_LEVEL", "debug");
+        let loaded = tracing::subscriber::with_default(subscriber, || {
+            ConfigLoader::new("ZALO_DEPRECATED_TEST_").load()
+        });
+        std::env::set_var("ZALO_DEPRECATED_TEST_LOGGING__FILTER", "warn");
+        let explicit = ConfigLoader::new("ZALO_DEPRECATED_TEST_").load();
+        std::env::remove_var("ZALO_DEPRECATED_TEST_LOG_LEVEL");
+        std::env::remove_var("ZALO_DEPRECATED_TEST_LOGGING__FILTER");
+
+        assert_eq!(loaded.expect("deprecated key").logging().filter(), "debug");
+        assert_eq!(fields.get("deprecated").as_deref(), Some("log_level"));
+        assert_eq!(fields.get("replacement").as_deref(), Some("logging.filter"));
+        assert_eq!(explicit.expect("explicit key").logging().filter(), "warn");
+    }
+
+    #[test]
+    fn fallback_prefix_applies_only_when_primary_is_unset() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let loader = ConfigLoader::new("ZALO_PRIMARY_TEST_")
+            .with_prefix_fallbacks(["ZALO_PRIMARY_TEST_", "ZALO_LEGACY_TEST_"]);
+        std::env::set_var("ZALO_LEGACY_TEST_ENVIRONMENT", "staging");
+        let fallback = loader.load();
+        std::env::set_var("ZALO_PRIMARY_TEST_ENVIRONMENT", "production");
+        let primary = loader.load();
+        std::env::remove_var("ZALO_LEGACY_TEST_ENVIRONMENT");
+        std::env::remove_var("ZALO_PRIMARY_TEST_ENVIRONMENT");
+
+        assert_eq!(
+            fallback.expect("fallback prefix").environment(),
+            Environment::Staging
+        );
+        assert_eq!(
+            primary.expect("primary prefix").environment(),
+            Environment::Production
+        );
+    }
+
+    #[test]
+    fn file_builders_select_a_single_requirement() {
+        let loader = ConfigLoader::new("ZALO_").with_required_file("config.toml");
+        assert_eq!(loader.file_requirement(), FileRequirement::Required);
+
+        let loader = loader.with_file_optional("config.toml");
+        assert_eq!(loader.file_requirement(), FileRequirement::Optional);
+        assert!(!loader.is_file_required());
+
+        let loader = loader.with_file_path("config.toml");
+        assert_eq!(loader.file_requirement(), FileRequirement::Overridable);
+        assert_eq!(
+            ConfigLoader::default().file_requirement(),
+            FileRequirement::Overridable
+        );
+    }
+
+    #[test]
+    fn optional_file_is_skipped_when_missing_and_merged_when_present() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let directory = tempfile::tempdir().expect("temp dir");
+        let path = directory.path().join("config.toml");
+        let loader = ConfigLoader::new("ZALO_OPTIONAL_TEST_").with_file_optional(&path);
+
+        assert_eq!(loader.load().expect("missing file"), AppConfig::default());
+        assert!(matches!(
+            ConfigLoader::new("ZALO_OPTIONAL_TEST_")
+                .with_file_path(&path)
+                .load(),
+            Err(TypesError::Config(ConfigError::MissingFile { .. }))
+        ));
+
+        write(&path, "environment = \"staging\"\n").expect("write config");
+        assert_eq!(
+            loader.load().expect("present file").environment(),
+            Environment::Staging
+        );
+    }
+
+    #[test]
+    fn prefix_is_read_from_environment_variable() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_PREFIX_VAR_TEST");
+        let unset = ConfigLoader::from_env_prefix_var("ZALO_PREFIX_VAR_TEST").expect("unset");
+
+        std::env::set_var("ZALO_PREFIX_VAR_TEST", "ZALO_TENANT_TEST_");
+        std::env::set_var("ZALO_TENANT_TEST_ENVIRONMENT", "staging");
+        let custom = ConfigLoader::from_env_prefix_var("ZALO_PREFIX_VAR_TEST").expect("set");
+        let loaded = custom.load();
+        std::env::remove_var("ZALO_PREFIX_VAR_TEST");
+        std::env::remove_var("ZALO_TENANT_TEST_ENVIRONMENT");
+
+        assert_eq!(unset.env_prefix(), "ZALO_BOT_");
+        assert_eq!(custom.env_prefix(), "ZALO_TENANT_TEST_");
+        assert_eq!(
+            loaded.expect("tenant config").environment(),
+            Environment::Staging
+        );
+    }
+
+    fn grace_seconds(value: JsonValue) -> JsonValue {
+        value
+            .as_u64()
+            .map_or(value, |secs| format!("{secs}s").into())
+    }
+
+    fn legacy_loader(path: &Path) -> ConfigLoader {
+        ConfigLoader::new("ZALO_MIGRATION_TEST_")
+            .with_file_path(path)
+            .with_migration(ConfigMigration::rename("retries", "retry.attempts"))
+            .with_migration(ConfigMigration::rename(
+                "retry.attempts",
+                "retry.max_retries",
+            ))
+            .with_migration(ConfigMigration::with_transform(
+                "grace_secs",
+                "shutdown_grace_period",
+                grace_seconds,
+            ))
+    }
+
+    #[test]
+    fn migrations_upgrade_old_format_in_order() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            "log_level = \"warn\"\nretries = 5\ngrace_secs = 45\n",
+        )
+        .expect("write config");
+
+        let (config, warnings) = legacy_loader(file.path())
+            .load_with_warnings()
+            .expect("migrated config");
+
+        assert_eq!(config.logging().filter(), "warn");
+        assert_eq!(config.retry().max_retries(), 5);
+        assert_eq!(config.shutdown_grace_period(), Duration::from_secs(45));
+        let renamed: Vec<_> = warnings
+            .iter()
+            .map(|warning| match warning {
+                ConfigWarning::DeprecatedKey { key, .. } => *key,
+                other => panic!("unexpected warning {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            renamed,
+            ["log_level", "retries", "retry.attempts", "grace_secs"]
+        );
+    }
+
+    #[test]
+    fn migrations_leave_current_format_untouched() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            "shutdown_grace_period = \"45s\"\n[logging]\nfilter = \"warn\"\nformat = \"json\"\n[retry]\nmax_retries = 5\n",
+        )
+        .expect("write config");
+
+        let (migrated, warnings) = legacy_loader(file.path())
+            .load_with_warnings()
+            .expect("current config");
+        let plain = ConfigLoader::new("ZALO_MIGRATION_TEST_")
+            .with_file_path(file.path())
+            .load()
+            .expect("current config");
+
+        assert_eq!(migrated, plain);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let document = serde_json::json!({ "logging": { "filter": "warn" } });
+        let migration = ConfigMigration::rename("log_level", "logging.filter");
+        assert_eq!(
+            migration.migrate(document.clone()).expect("migrate"),
+            document
+        );
+    }
+
+    #[test]
+    fn retry_block_loads_from_file_and_env() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            "[retry]\nmax_retries = 2\nbase_delay = \"500ms\"\nmax_delay = \"30s\"\njitter = false\n",
+        )
+        .expect("write config");
+        let loader = ConfigLoader::new("ZALO_RETRY_TEST_").with_file_path(file.path());
+
+        let from_file = loader.load().expect("valid retry block");
+        std::env::set_var("ZALO_RETRY_TEST_RETRY__MAX_RETRIES", "5");
+        let overridden = loader.load();
+        std::env::remove_var("ZALO_RETRY_TEST_RETRY__MAX_RETRIES");
+
+        let expected = RetryConfig::default()
+            .with_max_retries(2)
+            .with_delays(Duration::from_millis(500), Duration::from_secs(30))
+            .with_jitter(false);
+        assert_eq!(from_file.retry(), &expected);
+        assert_eq!(
+            overridden.expect("env override").retry(),
+            &expected.with_max_retries(5)
+        );
+    }
+
+    #[test]
+    fn retry_max_delay_must_cover_base_delay() {
+        let config = AppConfig::default().with_retry(
+            RetryConfig::default().with_delays(Duration::from_secs(5), Duration::from_secs(1)),
+        );
+
+        let error = config.validate().expect_err("inverted delays");
+
+        assert!(
+            matches!(error, ConfigError::Invalid { ref field, .. } if field == "retry.max_delay")
+        );
+    }
+
+    #[test]
+    fn preview_reports_pending_changes_without_applying_them() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            "[logging]\nfilter = \"info\"\nformat = \"json\"\n",
+        )
+        .expect("write config");
+        let loader = ConfigLoader::new("ZALO_PREVIEW_TEST_").with_file_path(file.path());
+        let current = loader.load().expect("current config");
+        let snapshot = current.clone();
+
+        write(
+            file.path(),
+            "[logging]\nfilter = \"debug\"\nformat = \"json\"\n[webhook]\nsecret = \"s3cret\"\n",
+        )
+        .expect("write config");
+        let changes = loader.preview_changes(&current).expect("preview");
+
+        assert_eq!(current, snapshot);
+        let summary: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            summary,
+            [
+                "logging.filter: \"info\" -> \"debug\"".to_owned(),
+                format!("webhook.secret: <unset> -> \"{REDACTED}\""),
+            ]
+        );
+        assert!(current.diff(&current).expect("diff").is_empty());
+    }
+
+    #[test]
+    fn environments_order_towards_production() {
+        use Environment::{Development, Production, Staging};
+
+        assert!(Development < Staging && Staging < Production);
+        let mut environments = [Production, Development, Staging];
+        environments.sort();
+        assert_eq!(environments, [Development, Staging, Production]);
+
+        assert!(Staging.is_at_least(Staging));
+        assert!(Production.is_at_least(Staging));
+        assert!(!Development.is_at_least(Staging));
+        assert!(Development.is_at_least(Development));
+        assert!(!Staging.is_at_least(Production));
+    }
+
+    #[test]
+    fn strict_env_reports_single_underscore_nested_keys() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let loader = ConfigLoader::new("ZALO_STRICT_ENV_TEST_").with_strict_env(true);
+
+        std::env::set_var("ZALO_STRICT_ENV_TEST_LOGGING_FILTER", "debug");
+        let typo = loader.load_with_warnings();
+        std::env::remove_var("ZALO_STRICT_ENV_TEST_LOGGING_FILTER");
+        std::env::set_var("ZALO_STRICT_ENV_TEST_LOGGING__FILTER", "debug");
+        let correct = loader.load_with_warnings();
+        std::env::remove_var("ZALO_STRICT_ENV_TEST_LOGGING__FILTER");
+        std::env::set_var("ZALO_STRICT_ENV_TEST_LOG_LEVEL", "debug");
+        let deprecated = loader.load_with_warnings();
+        std::env::remove_var("ZALO_STRICT_ENV_TEST_LOG_LEVEL");
+
+        let (config, warnings) = typo.expect("typo config");
+        assert_eq!(config.logging().filter(), "info");
+        assert_eq!(
+            warnings,
+            [ConfigWarning::UnknownEnvKey {
+                variable: "ZALO_STRICT_ENV_TEST_LOGGING_FILTER".to_owned(),
+                suggestion: Some("ZALO_STRICT_ENV_TEST_LOGGING__FILTER".to_owned()),
+            }]
+        );
+        let (config, warnings) = correct.expect("correct config");
+        assert_eq!(config.logging().filter(), "debug");
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let (_, warnings) = deprecated.expect("deprecated config");
+        assert_eq!(
+            warnings,
+            [ConfigWarning::DeprecatedKey {
+                key: "log_level",
+                replacement: "logging.filter",
+            }]
+        );
+    }
+
+    #[test]
+    fn known_env_keys_cover_every_config_field() {
+        // Populate the optional fields so that every key is serialised.
+        let config = AppConfig::default()
+            .with_logging(LoggingConfig::default().with_file_output("logs"))
+            .with_webhook(
+                WebhookConfig::default()
+                    .with_secret("secret")
+                    .with_secrets(["previous"])
+                    .with_secret_base64("c2VjcmV0")
+                    .with_tolerance(Duration::from_secs(60)),
+            );
+        let mut fields = Vec::new();
+        let mut pending = vec![(
+            String::new(),
+            serde_json::to_value(config).expect("serialise"),
+        )];
+        while let Some((path, value)) = pending.pop() {
+            match value {
+                JsonValue::Object(fields) if path != "webhook.secrets" => {
+                    for (name, value) in fields {
+                        let key = if path.is_empty() {
+                            name
+                        } else {
+                            format!("{path}.{name}")
+                        };
+                        pending.push((key, value));
+                    }
+                }
+                _ => {
+                    assert!(KNOWN_ENV_KEYS.contains(&path.as_str()), "{path}");
+                    fields.push(path);
+                }
+            }
+        }
+        for key in KNOWN_ENV_KEYS {
+            assert!(fields.iter().any(|field| field == key), "{key}");
+        }
     }
 
-    /// Returns the configured file path, if any.
-    #[must_use]
-    pub fn file_path(&self) -> Option<&Path> {
-        self.file_path.as_deref()
+    #[test]
+    fn shared_config_clones_share_allocation() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let shared = ConfigLoader::new("ZALO_SHARED_TEST_")
+            .load_shared()
+            .expect("shared config");
+        let clone = SharedConfig::clone(&shared);
+
+        assert!(Arc::ptr_eq(&shared, &clone));
+        assert_eq!(shared.environment(), Environment::Development);
     }
 
-    /// Loads the configuration from the configured sources.
-    ///
-    /// Environment variables take precedence over file values and defaults.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`TypesError::Config`] when the configuration file is missing or
-    /// the model fails validation.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use zalo_types::ConfigLoader;
-    ///
-    /// let result = ConfigLoader::default().load();
-    /// assert!(result.is_ok());
-    /// ```
-    pub fn load(&self) -> TypesResult<AppConfig> {
-        let mut figment = Figment::from(Serialized::defaults(AppConfig::default()));
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_load_matches_sync_load() {
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            "environment = \"staging\"\n\n[logging]\nfilter = \"debug\"\n",
+        )
+        .expect("write config");
+        let loader = ConfigLoader::new("ZALO_ASYNC_TEST_").with_file_path(file.path());
+
+        let asynchronous = loader.load_async().await.expect("async load");
+
+        assert_eq!(asynchronous, loader.load().expect("sync load"));
+        assert_eq!(asynchronous.environment(), Environment::Staging);
+    }
+
+    #[test]
+    fn webhook_tolerance_defaults_by_environment() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let development = AppConfig::default();
+        let production = AppConfig::default().with_environment(Environment::Production);
+        assert_eq!(
+            development.effective_webhook_tolerance(),
+            Duration::from_secs(600)
+        );
+        assert_eq!(
+            production.effective_webhook_tolerance(),
+            Duration::from_secs(300)
+        );
+
+        std::env::set_var("ZALO_TOLERANCE_TEST_ENVIRONMENT", "production");
+        std::env::set_var("ZALO_TOLERANCE_TEST_WEBHOOK__TOLERANCE", "45s");
+        let loaded = ConfigLoader::new("ZALO_TOLERANCE_TEST_").load();
+        std::env::remove_var("ZALO_TOLERANCE_TEST_ENVIRONMENT");
+        std::env::remove_var("ZALO_TOLERANCE_TEST_WEBHOOK__TOLERANCE");
+
+        let loaded = loaded.expect("explicit tolerance");
+        assert_eq!(loaded.webhook().tolerance(), Some(Duration::from_secs(45)));
+        assert_eq!(
+            loaded.effective_webhook_tolerance(),
+            Duration::from_secs(45)
+        );
+    }
 
-        let env_path = env_config_path(&self.env_prefix);
-        let resolved_path = env_path.as_deref().or(self.file_path.as_deref());
+    #[test]
+    fn rejects_conflicting_secret_options() {
+        let cases = [
+            (
+                WebhookConfig::default()
+                    .with_secret("primary")
+                    .with_secrets(["primary", "previous"]),
+                "webhook.secrets",
+                "webhook.secret",
+            ),
+            (
+                WebhookConfig::default()
+                    .with_secret("primary")
+                    .with_secret_base64("c2VjcmV0"),
+                "webhook.secret_base64",
+                "webhook.secret",
+            ),
+            (
+                WebhookConfig::default()
+                    .with_secrets(["current"])
+                    .with_secret_base64("c2VjcmV0"),
+                "webhook.secret_base64",
+                "webhook.secrets",
+            ),
+        ];
 
-        if let Some(path) = resolved_path {
-            if !path_exists(path) {
-                return Err(ConfigError::MissingFile {
-                    path: path.to_path_buf(),
+        for (webhook, expected_field, conflicting) in cases {
+            let error = AppConfig::default()
+                .with_webhook(webhook)
+                .validate()
+                .expect_err("conflicting secrets");
+            match error {
+                ConfigError::Invalid { field, reason } => {
+                    assert_eq!(field, expected_field);
+                    assert!(reason.contains(conflicting), "{reason}");
                 }
-                .into());
+                other => panic!("unexpected error: {other:?}"),
             }
-            figment = figment.merge(Toml::file(path));
         }
 
-        figment = figment.merge(Env::prefixed(&self.env_prefix).split("__"));
-
-        figment
-            .extract::<AppConfig>()
-            .map_err(ConfigError::from)
-            .map_err(TypesError::from)
+        let base64_only = WebhookConfig::default().with_secret_base64("c2VjcmV0");
+        assert!(base64_only.validate().is_ok());
+        assert!(base64_only.has_secret());
+        assert_eq!(base64_only.redacted().secret_base64(), None);
     }
-}
 
-impl Default for ConfigLoader {
-    fn default() -> Self {
-        Self::new("ZALO_BOT_")
+    #[test]
+    fn lowered_production_ceiling_suppresses_debug_warning() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::set_var("ZALO_CEILING_TEST_ENVIRONMENT", "production");
+        std::env::set_var("ZALO_CEILING_TEST_LOGGING__FILTER", "debug");
+        std::env::set_var("ZALO_CEILING_TEST_LOGGING__PRODUCTION_MAX_LEVEL", "info");
+        let loaded = ConfigLoader::new("ZALO_CEILING_TEST_").load_with_warnings();
+        std::env::remove_var("ZALO_CEILING_TEST_ENVIRONMENT");
+        std::env::remove_var("ZALO_CEILING_TEST_LOGGING__FILTER");
+        std::env::remove_var("ZALO_CEILING_TEST_LOGGING__PRODUCTION_MAX_LEVEL");
+
+        let (config, warnings) = loaded.expect("production config");
+        assert_eq!(config.effective_filter(), "info");
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, ConfigWarning::DebugLoggingInProduction { .. })));
     }
-}
 
-fn path_exists(path: &Path) -> bool {
-    path.exists()
-}
+    #[test]
+    fn load_with_warnings_reports_non_fatal_issues() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::set_var("ZALO_WARNINGS_TEST_LOG_LEVEL", "debug");
+        let deprecated = ConfigLoader::new("ZALO_WARNINGS_TEST_").load_with_warnings();
+        std::env::set_var("ZALO_WARNINGS_TEST_ENVIRONMENT", "production");
+        std::env::set_var("ZALO_WARNINGS_TEST_LOGGING__FORMAT", "json");
+        let production = ConfigLoader::new("ZALO_WARNINGS_TEST_").load_with_warnings();
+        std::env::remove_var("ZALO_WARNINGS_TEST_LOG_LEVEL");
+        std::env::remove_var("ZALO_WARNINGS_TEST_ENVIRONMENT");
+        std::env::remove_var("ZALO_WARNINGS_TEST_LOGGING__FORMAT");
 
-fn env_config_path(prefix: &str) -> Option<PathBuf> {
-    let mut key = String::with_capacity(prefix.len() + "CONFIG_PATH".len());
-    key.push_str(prefix);
-    key.push_str("CONFIG_PATH");
+        let (config, warnings) = deprecated.expect("deprecated key");
+        assert_eq!(config.logging().filter(), "debug");
+        assert_eq!(
+            warnings,
+            [ConfigWarning::DeprecatedKey {
+                key: "log_level",
+                replacement: "logging.filter",
+            }]
+        );
 
-    let value = env::var(&key).ok()?;
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        None
-    } else {
-        Some(PathBuf::from(trimmed))
+        let (_, warnings) = production.expect("production config");
+        assert!(warnings.contains(&ConfigWarning::DebugLoggingInProduction {
+            filter: "debug".to_owned(),
+        }));
+        assert!(warnings[warnings.len() - 1]
+            .to_string()
+            .contains("debug logging in production"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::write;
-    use std::sync::Mutex;
+    #[test]
+    fn merges_json_value_below_environment() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let value = serde_json::json!({
+            "environment": "staging",
+            "logging": { "filter": "debug" }
+        });
+        let loader = ConfigLoader::new("ZALO_JSON_TEST_").with_json_value(value);
 
-    use tempfile::NamedTempFile;
+        let merged = loader.load().expect("json layer");
+        std::env::set_var("ZALO_JSON_TEST_ENVIRONMENT", "production");
+        let overridden = loader.load();
+        std::env::remove_var("ZALO_JSON_TEST_ENVIRONMENT");
 
-    static ENV_GUARD: Mutex<()> = Mutex::new(());
+        assert_eq!(merged.environment(), Environment::Staging);
+        assert_eq!(merged.logging().filter(), "debug");
+        let overridden = overridden.expect("env layer");
+        assert_eq!(overridden.environment(), Environment::Production);
+        assert_eq!(overridden.logging().filter(), "debug");
+
+        let error = ConfigLoader::new("ZALO_JSON_TEST_")
+            .with_json_value(serde_json::json!(["not", "a", "map"]))
+            .load()
+            .expect_err("invalid shape");
+        assert!(matches!(
+            error,
+            TypesError::Config(ConfigError::Extraction { .. })
+        ));
+    }
 
     #[test]
-    fn loads_default_configuration() {
+    fn reports_malformed_durations_as_invalid_fields() {
         let _guard = ENV_GUARD.lock().expect("lock poisoned");
-        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
-        std::env::remove_var("ZALO_BOT_LOGGING__FILTER");
-        std::env::remove_var("ZALO_BOT_LOGGING__FORMAT");
-        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
-
-        let config = ConfigLoader::default()
-            .load()
-            .expect("default configuration should load");
+        std::env::set_var("ZALO_DURATION_TEST_RETRY__BASE_DELAY", "soon");
+        let loaded = ConfigLoader::new("ZALO_DURATION_TEST_").load();
+        std::env::remove_var("ZALO_DURATION_TEST_RETRY__BASE_DELAY");
 
-        assert_eq!(config.environment(), Environment::Development);
-        assert_eq!(config.logging().filter(), "info");
-        assert_eq!(config.logging().format(), LogFormat::Text);
+        let error = loaded.expect_err("malformed duration");
+        assert!(
+            matches!(
+                error,
+                TypesError::Config(ConfigError::Invalid { ref field, .. })
+                    if field == "retry.base_delay"
+            ),
+            "{error}"
+        );
     }
 
     #[test]
-    fn merges_environment_variables() {
+    fn validates_filter_while_loading() {
         let _guard = ENV_GUARD.lock().expect("lock poisoned");
-        std::env::set_var("ZALO_BOT_ENVIRONMENT", "production");
-        std::env::set_var("ZALO_BOT_LOGGING__FILTER", "debug");
-        std::env::set_var("ZALO_BOT_LOGGING__FORMAT", "json");
-        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        std::env::set_var("ZALO_FILTER_TEST_LOGGING__FILTER", "info,zalo_bot=debug");
+        let valid = ConfigLoader::new("ZALO_FILTER_TEST_").load();
+        std::env::set_var("ZALO_FILTER_TEST_LOGGING__FILTER", "=info");
+        let invalid = ConfigLoader::new("ZALO_FILTER_TEST_").load();
+        std::env::remove_var("ZALO_FILTER_TEST_LOGGING__FILTER");
 
-        let config = ConfigLoader::default()
-            .load()
-            .expect("config should respect env overrides");
+        assert_eq!(
+            valid.expect("valid filter").logging().filter(),
+            "info,zalo_bot=debug"
+        );
+        let error = invalid.expect_err("invalid filter");
+        assert!(matches!(
+            error,
+            TypesError::Config(ConfigError::Extraction { .. })
+        ));
+        assert!(error.to_string().contains("invalid filter `=info`"));
+    }
 
-        std::env::remove_var("ZALO_BOT_ENVIRONMENT");
-        std::env::remove_var("ZALO_BOT_LOGGING__FILTER");
-        std::env::remove_var("ZALO_BOT_LOGGING__FORMAT");
+    #[test]
+    fn detects_global_debug_logging() {
+        let with_filter = |filter: &str| {
+            AppConfig::default().with_logging(LoggingConfig::new(filter, LogFormat::Text))
+        };
 
-        assert_eq!(config.environment(), Environment::Production);
-        assert_eq!(config.logging().filter(), "debug");
-        assert_eq!(config.logging().format(), LogFormat::Json);
+        assert!(!with_filter("info").is_debug_logging());
+        assert!(with_filter("debug").is_debug_logging());
+        assert!(with_filter("zalo_bot=info, TRACE").is_debug_logging());
+        assert!(!with_filter("info,zalo_bot=debug").is_debug_logging());
+        assert!(!with_filter("debug,info").is_debug_logging());
+        assert!(with_filter("4").is_debug_logging());
+        assert!(!with_filter("3").is_debug_logging());
     }
 
     #[test]
@@ -379,6 +3185,32 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn shutdown_grace_period_defaults_and_overrides() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        assert_eq!(
+            AppConfig::default().shutdown_grace_period(),
+            Duration::from_secs(30)
+        );
+
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), "shutdown_grace_period = \"1m 30s\"\n").expect("write config");
+        let loader = ConfigLoader::new("ZALO_GRACE_TEST_").with_file_path(file.path());
+        let from_file = loader.load();
+        std::env::set_var("ZALO_GRACE_TEST_SHUTDOWN_GRACE_PERIOD", "10s");
+        let from_env = loader.load();
+        std::env::remove_var("ZALO_GRACE_TEST_SHUTDOWN_GRACE_PERIOD");
+
+        assert_eq!(
+            from_file.expect("file override").shutdown_grace_period(),
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            from_env.expect("env override").shutdown_grace_period(),
+            Duration::from_secs(10)
+        );
+    }
+
     #[test]
     fn loads_from_toml_file() {
         let _guard = ENV_GUARD.lock().expect("lock poisoned");
@@ -405,6 +3237,270 @@ mod tests {
         assert_eq!(config.logging().format(), LogFormat::Text);
     }
 
+    #[test]
+    fn required_file_loads_when_present() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), "environment = \"production\"\n").expect("write config");
+
+        let config = ConfigLoader::default()
+            .with_required_file(file.path())
+            .load()
+            .expect("required file should load");
+
+        assert_eq!(config.environment(), Environment::Production);
+    }
+
+    #[test]
+    fn required_file_errors_when_missing() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+
+        let error = ConfigLoader::default()
+            .with_required_file("/definitely/missing.toml")
+            .load()
+            .expect_err("missing required file should error");
+
+        assert!(matches!(
+            error,
+            TypesError::Config(ConfigError::MissingFile { .. })
+        ));
+    }
+
+    #[test]
+    fn required_file_rejects_env_path_override() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        let required = NamedTempFile::new().expect("required file");
+        let other = NamedTempFile::new().expect("other file");
+        std::env::set_var("ZALO_BOT_CONFIG_PATH", other.path());
+
+        let error = ConfigLoader::default()
+            .with_required_file(required.path())
+            .load()
+            .expect_err("override should be rejected");
+
+        std::env::set_var("ZALO_BOT_CONFIG_PATH", required.path());
+        let same_path = ConfigLoader::default()
+            .with_required_file(required.path())
+            .load();
+
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+
+        assert!(matches!(
+            error,
+            TypesError::Config(ConfigError::RequiredFileOverridden { .. })
+        ));
+        assert!(same_path.is_ok());
+    }
+
+    #[test]
+    fn loads_secret_rotation_list_from_toml() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            r#"
+                [webhook]
+                secrets = ["current", "previous"]
+            "#,
+        )
+        .expect("write config");
+
+        let config = ConfigLoader::default()
+            .with_file_path(file.path())
+            .load()
+            .expect("rotation list should load");
+
+        assert_eq!(config.webhook().resolved_secrets(), ["current", "previous"]);
+    }
+
+    #[test]
+    fn loads_secret_rotation_list_from_env() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+
+        std::env::set_var("ZALO_BOT_WEBHOOK__SECRETS", "current, previous");
+        let comma_separated = ConfigLoader::default().load();
+        std::env::remove_var("ZALO_BOT_WEBHOOK__SECRETS");
+
+        std::env::set_var("ZALO_BOT_WEBHOOK__SECRETS__1", "previous");
+        std::env::set_var("ZALO_BOT_WEBHOOK__SECRETS__0", "current");
+        let indexed = ConfigLoader::default().load();
+        std::env::remove_var("ZALO_BOT_WEBHOOK__SECRETS__0");
+        std::env::remove_var("ZALO_BOT_WEBHOOK__SECRETS__1");
+
+        for config in [comma_separated, indexed] {
+            let config = config.expect("env rotation list should load");
+            assert_eq!(config.webhook().secrets(), ["current", "previous"]);
+        }
+    }
+
+    #[test]
+    fn rejects_secret_and_rotation_list_together() {
+        let _guard = ENV_GUARD.lock().expect("lock poisoned");
+        std::env::remove_var("ZALO_BOT_CONFIG_PATH");
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            r#"
+                [webhook]
+                secret = "current"
+                secrets = ["current", "previous"]
+            "#,
+        )
+        .expect("write config");
+
+        let error = ConfigLoader::default()
+            .with_file_path(file.path())
+            .load()
+            .expect_err("conflicting secrets should error");
+
+        assert!(matches!(
+            error,
+            TypesError::Config(ConfigError::Invalid { ref field, .. }) if field == "webhook.secrets"
+        ));
+    }
+
+    #[test]
+    fn strict_mode_enforces_structured_production_logs() {
+        let config = |environment, format| {
+            AppConfig::default()
+                .with_strict(true)
+                .with_environment(environment)
+                .with_logging(LoggingConfig::new("info", format))
+        };
+
+        let error = config(Environment::Production, LogFormat::Text)
+            .validate()
+            .expect_err("text logs in production");
+        assert!(
+            matches!(error, ConfigError::Invalid { ref field, .. } if field == "logging.format")
+        );
+        config(Environment::Production, LogFormat::Json)
+            .validate()
+            .expect("json logs in production");
+        config(Environment::Development, LogFormat::Text)
+            .validate()
+            .expect("text logs in development");
+        config(Environment::Production, LogFormat::Text)
+            .with_strict(false)
+            .validate()
+            .expect("guardrail is opt-in");
+    }
+
+    #[test]
+    fn both_output_requires_directory() {
+        let logging = LoggingConfig::default().with_output(LogOutput::Both);
+        let error = AppConfig::default()
+            .with_logging(logging)
+            .validate()
+            .expect_err("directory missing");
+
+        assert!(
+            matches!(error, ConfigError::Invalid { ref field, .. } if field == "logging.directory")
+        );
+        AppConfig::default()
+            .with_logging(LoggingConfig::default().with_file_output("logs"))
+            .validate()
+            .expect("file output preset is valid");
+    }
+
+    #[test]
+    fn loads_file_with_bom_and_crlf() {
+        let file = NamedTempFile::new().expect("temp file");
+        write(
+            file.path(),
+            "\u{feff}environment = \"staging\"\r\n\r\n[logging]\r\nfilter = \"debug\"\r\nformat = \"json\"\r\n",
+        )
+        .expect("write config");
+
+        let config = ConfigLoader::new("ZALO_WINDOWS_TEST_")
+            .with_file_path(file.path())
+            .load()
+            .expect("windows-authored config should load");
+
+        assert_eq!(config.environment(), Environment::Staging);
+        assert_eq!(config.logging().filter(), "debug");
+        assert_eq!(config.logging().format(), LogFormat::Json);
+    }
+
+    #[test]
+    fn file_errors_name_the_file() {
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), "[retry]\nmax_retries = \"many\"\n").expect("write config");
+
+        let error = ConfigLoader::new("ZALO_FILE_ERROR_TEST_")
+            .with_file_path(file.path())
+            .load()
+            .expect_err("invalid retry count");
+
+        let message = error.to_string();
+        assert!(
+            message.contains(&file.path().display().to_string()),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn normalize_source_only_strips_leading_bom() {
+        assert_eq!(normalize_source("\u{feff}a = 1\r\n"), "a = 1\n");
+        assert_eq!(normalize_source("a = \"\u{feff}\"\n"), "a = \"\u{feff}\"\n");
+    }
+
+    #[test]
+    fn exported_toml_round_trips_without_secrets() {
+        let config = AppConfig::default()
+            .with_environment(Environment::Production)
+            .with_logging(LoggingConfig::new("debug", LogFormat::Json))
+            .with_webhook(WebhookConfig::default().with_secrets(["current", "previous"]));
+        let exported = config.to_toml().expect("export");
+
+        assert!(!exported.contains("current"));
+        assert!(!exported.contains("previous"));
+
+        let file = NamedTempFile::new().expect("temp file");
+        write(file.path(), &exported).expect("write export");
+        let reloaded = ConfigLoader::new("ZALO_EXPORT_TEST_")
+            .with_file_path(file.path())
+            .load()
+            .expect("exported config should load");
+
+        assert_eq!(
+            reloaded,
+            config.clone().with_webhook(config.webhook().redacted())
+        );
+    }
+
+    #[test]
+    fn webhook_debug_redacts_secrets() {
+        let webhook = WebhookConfig::default().with_secret("top-secret");
+
+        assert!(!format!("{webhook:?}").contains("top-secret"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_unicode_config_path_is_rejected() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        std::env::set_var(
+            "ZALO_NON_UTF8_TEST_CONFIG_PATH",
+            OsString::from_vec(vec![0x2f, 0xff, 0xfe]),
+        );
+        let result = ConfigLoader::new("ZALO_NON_UTF8_TEST_").load();
+        std::env::remove_var("ZALO_NON_UTF8_TEST_CONFIG_PATH");
+
+        let error = result.expect_err("non-unicode path must be rejected");
+        assert!(matches!(
+            error,
+            TypesError::Config(ConfigError::Invalid { ref field, .. })
+                if field == "ZALO_NON_UTF8_TEST_CONFIG_PATH"
+        ));
+    }
+
     #[test]
     fn env_config_path_missing_file_errors() {
         let _guard = ENV_GUARD.lock().expect("lock poisoned");
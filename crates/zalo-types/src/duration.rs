@@ -0,0 +1,188 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::ConfigError;
+
+/// Prefix of every message rejecting a duration, which lets the loader
+/// report such failures as [`ConfigError::Invalid`].
+pub(crate) const INVALID_DURATION: &str = "invalid duration";
+
+/// [`Duration`] accepted in configuration as a human-readable string or as
+/// whole seconds.
+///
+/// Strings use the `humantime` syntax (`"30s"`, `"2m"`, `"1h 30m"`,
+/// `"250ms"`); integers are interpreted as seconds. The value serialises back
+/// to the human-readable form.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use zalo_types::HumanDuration;
+///
+/// let timeout = HumanDuration::parse("request_timeout", "1m 30s")?;
+/// assert_eq!(timeout.as_duration(), Duration::from_secs(90));
+/// assert_eq!(timeout.to_string(), "1m 30s");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HumanDuration(Duration);
+
+impl HumanDuration {
+    /// Wraps a duration.
+    #[must_use]
+    pub const fn new(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    /// Parses a human-readable duration for the named configuration field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] naming `field` when the input is not
+    /// a valid duration.
+    pub fn parse(field: &str, input: &str) -> Result<Self, ConfigError> {
+        parse_human(input)
+            .map(Self)
+            .map_err(|reason| ConfigError::Invalid {
+                field: field.to_owned(),
+                reason,
+            })
+    }
+
+    /// Returns the wrapped duration.
+    #[must_use]
+    pub const fn as_duration(self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(duration: HumanDuration) -> Self {
+        duration.0
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        humantime::format_duration(self.0).fmt(formatter)
+    }
+}
+
+impl Serialize for HumanDuration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(HumanDurationVisitor)
+    }
+}
+
+struct HumanDurationVisitor;
+
+impl<'de> Visitor<'de> for HumanDurationVisitor {
+    type Value = HumanDuration;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a duration such as \"30s\" or a number of seconds")
+    }
+
+    fn visit_u64<E>(self, seconds: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(HumanDuration(Duration::from_secs(seconds)))
+    }
+
+    fn visit_i64<E>(self, seconds: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        u64::try_from(seconds)
+            .map(|seconds| HumanDuration(Duration::from_secs(seconds)))
+            .map_err(|_| E::custom(format!("{INVALID_DURATION} `{seconds}`: negative")))
+    }
+
+    fn visit_str<E>(self, input: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        parse_human(input).map(HumanDuration).map_err(E::custom)
+    }
+}
+
+fn parse_human(input: &str) -> Result<Duration, String> {
+    humantime::parse_duration(input.trim())
+        .map_err(|error| format!("{INVALID_DURATION} `{input}`: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use figment::providers::{Format, Toml};
+    use figment::Figment;
+
+    #[derive(Debug, Deserialize)]
+    struct Timeouts {
+        timeout: HumanDuration,
+    }
+
+    fn extract(toml: &str) -> Result<Duration, Box<figment::Error>> {
+        Figment::from(Toml::string(toml))
+            .extract::<Timeouts>()
+            .map(|timeouts| timeouts.timeout.as_duration())
+            .map_err(Box::new)
+    }
+
+    #[test]
+    fn parses_human_strings_and_seconds() {
+        let cases = [
+            ("timeout = \"30s\"", Duration::from_secs(30)),
+            ("timeout = \"2m\"", Duration::from_secs(120)),
+            ("timeout = \"1h 15m\"", Duration::from_secs(4_500)),
+            ("timeout = \"250ms\"", Duration::from_millis(250)),
+            ("timeout = 45", Duration::from_secs(45)),
+        ];
+
+        for (toml, expected) in cases {
+            assert_eq!(extract(toml).expect(toml), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        for toml in ["timeout = \"soon\"", "timeout = -5"] {
+            let error = ConfigError::from(*extract(toml).expect_err(toml));
+            assert!(
+                matches!(error, ConfigError::Invalid { ref field, .. } if field == "timeout"),
+                "{error}"
+            );
+        }
+
+        let error = HumanDuration::parse("request_timeout", "30 parsecs").expect_err("invalid");
+        assert!(matches!(
+            error,
+            ConfigError::Invalid { ref field, .. } if field == "request_timeout"
+        ));
+    }
+}
@@ -1,10 +1,13 @@
 use std::error::Error as StdError;
 use std::path::PathBuf;
 
+use figment::error::{Actual, Kind};
 use figment::Error as FigmentError;
 use masterror::{AppError, AppErrorKind, AppResult};
 use thiserror::Error;
 
+use crate::duration::INVALID_DURATION;
+
 /// Result alias for operations in the `zalo-types` crate.
 ///
 /// The alias guarantees that all fallible operations map to [`TypesError`],
@@ -79,6 +82,33 @@ pub enum ConfigError {
         /// Path to the configuration file that could not be found.
         path: PathBuf,
     },
+    /// Configuration file exists but could not be read.
+    #[error("failed to read configuration file {path}: {source}")]
+    Read {
+        /// Path to the configuration file.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// A required configuration file was redirected by the path override.
+    #[error(
+        "configuration must be loaded from {required}, but the path override points to {actual}"
+    )]
+    RequiredFileOverridden {
+        /// Path the loader was required to use.
+        required: PathBuf,
+        /// Path supplied through the environment override.
+        actual: PathBuf,
+    },
+    /// A configuration value violates a semantic constraint.
+    #[error("invalid configuration for `{field}`: {reason}")]
+    Invalid {
+        /// Dotted path of the offending field.
+        field: String,
+        /// Human-readable explanation of the violation.
+        reason: String,
+    },
     /// Figment was unable to extract the configuration model.
     #[error("failed to extract configuration: {source}")]
     Extraction {
@@ -88,14 +118,132 @@ pub enum ConfigError {
     },
 }
 
+/// Error returned when a string does not name a supported log format.
+///
+/// # Examples
+///
+/// ```
+/// use zalo_types::LogFormat;
+///
+/// let error = "xml".parse::<LogFormat>().unwrap_err();
+/// assert_eq!(error.input(), "xml");
+/// ```
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+#[error(
+    "unknown log format `{input}`, expected one of: text, plain, human, json, structured, bunyan, msgpack"
+)]
+pub struct ParseLogFormatError {
+    input: String,
+}
+
+impl ParseLogFormatError {
+    pub(crate) fn new(input: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+        }
+    }
+
+    /// Returns the rejected input.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
 impl From<FigmentError> for ConfigError {
+    /// Reports a single malformed duration as [`ConfigError::Invalid`] naming
+    /// the field; otherwise wraps the extraction error, scrubbing values of
+    /// secret-like keys so the message can be logged safely.
     fn from(error: FigmentError) -> Self {
+        if let Some(invalid) = invalid_duration(&error) {
+            return invalid;
+        }
+
         Self::Extraction {
-            source: Box::new(error),
+            source: Box::new(redact_sensitive_values(error)),
+        }
+    }
+}
+
+fn invalid_duration(error: &FigmentError) -> Option<ConfigError> {
+    match &error.kind {
+        Kind::Message(message)
+            if error.count() == 1
+                && message.starts_with(INVALID_DURATION)
+                && !is_sensitive_path(&error.path) =>
+        {
+            Some(ConfigError::Invalid {
+                field: error.path.join("."),
+                reason: message.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Key fragments marking a configuration value as sensitive.
+const SENSITIVE_KEY_MARKERS: [&str; 3] = ["secret", "token", "password"];
+
+/// Placeholder substituted for scrubbed values.
+pub const REDACTED: &str = "***";
+
+fn redact_sensitive_values(error: FigmentError) -> FigmentError {
+    let errors = error
+        .into_iter()
+        .map(|mut error| {
+            if is_sensitive_path(&error.path) {
+                error.kind = redact_kind(error.kind);
+            }
+            error
+        })
+        .collect::<Vec<_>>();
+
+    // `a.chain(b)` returns `b` followed by `a`, so rebuild from the tail.
+    errors
+        .into_iter()
+        .rev()
+        .reduce(FigmentError::chain)
+        .unwrap_or_else(|| FigmentError::from("configuration extraction failed"))
+}
+
+fn is_sensitive_path(path: &[String]) -> bool {
+    path.iter().any(|segment| {
+        let segment = segment.to_ascii_lowercase();
+        SENSITIVE_KEY_MARKERS
+            .iter()
+            .any(|marker| segment.contains(marker))
+    })
+}
+
+fn redact_kind(kind: Kind) -> Kind {
+    match kind {
+        Kind::Message(_) => Kind::Message(format!("invalid value `{REDACTED}`")),
+        Kind::InvalidType(actual, expected) => Kind::InvalidType(redact_actual(actual), expected),
+        Kind::InvalidValue(actual, expected) => Kind::InvalidValue(redact_actual(actual), expected),
+        Kind::UnknownVariant(_, expected) => Kind::UnknownVariant(REDACTED.to_owned(), expected),
+        Kind::Unsupported(actual) => Kind::Unsupported(redact_actual(actual)),
+        Kind::ISizeOutOfRange(_) | Kind::USizeOutOfRange(_) => {
+            Kind::Message(format!("integer `{REDACTED}` is out of range"))
         }
+        other => other,
     }
 }
 
+fn redact_actual(actual: Actual) -> Actual {
+    let kind = match actual {
+        Actual::Bool(_) => "bool",
+        Actual::Unsigned(_) => "unsigned int",
+        Actual::Signed(_) => "signed int",
+        Actual::Float(_) => "float",
+        Actual::Char(_) => "char",
+        Actual::Str(_) => "string",
+        Actual::Bytes(_) => "bytes",
+        other => return other,
+    };
+
+    Actual::Other(format!("{kind} `{REDACTED}`"))
+}
+
 impl From<ConfigError> for AppError {
     fn from(error: ConfigError) -> Self {
         AppError::with(AppErrorKind::Config, error.to_string())
@@ -115,6 +263,40 @@ impl From<TypesError> for AppError {
 mod tests {
     use super::*;
 
+    use figment::providers::Serialized;
+    use figment::Figment;
+
+    fn extraction_message(key: &str) -> String {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Settings {
+            #[serde(default)]
+            api_token: String,
+            #[serde(default)]
+            name: String,
+        }
+
+        let error = Figment::from(Serialized::default(key, 987_654_321_u64))
+            .extract::<Settings>()
+            .expect_err("integer is not a string");
+        ConfigError::from(error).to_string()
+    }
+
+    #[test]
+    fn extraction_error_scrubs_secret_values() {
+        let message = extraction_message("api_token");
+
+        assert!(!message.contains("987654321"), "{message}");
+        assert!(message.contains(REDACTED), "{message}");
+    }
+
+    #[test]
+    fn extraction_error_keeps_other_values() {
+        let message = extraction_message("name");
+
+        assert!(message.contains("987654321"), "{message}");
+    }
+
     #[test]
     fn config_error_maps_to_app_error() {
         let error = ConfigError::MissingFile {
@@ -135,7 +317,7 @@ mod tests {
 
     #[test]
     fn with_source_attaches_context() {
-        let source = std::io::Error::new(std::io::ErrorKind::Other, "io");
+        let source = std::io::Error::other("io");
         let error = TypesError::with_message("failure").with_source(source);
 
         match error {
@@ -5,11 +5,21 @@
 //! The crate contains reusable configuration models and error definitions that
 //! can be leveraged by both the bot server and the mini app SDK.
 
+/// Cached configuration access with explicit reloads.
+pub mod cache;
 /// Runtime configuration facilities.
 pub mod config;
+/// Human-readable duration values for configuration fields.
+pub mod duration;
 /// Core error types and aliases.
 pub mod error;
 
-pub use config::{AppConfig, ConfigLoader, Environment, LogFormat, LoggingConfig};
-pub use error::{ConfigError, TypesError, TypesResult};
+pub use cache::CachedConfigLoader;
+pub use config::{
+    AppConfig, ConfigChange, ConfigLoader, ConfigMigration, ConfigWarning, Environment,
+    FileRequirement, LogFormat, LogOutput, LoggingConfig, RetryConfig, SharedConfig, WebhookConfig,
+    MAX_RETRIES_LIMIT,
+};
+pub use duration::HumanDuration;
+pub use error::{ConfigError, ParseLogFormatError, TypesError, TypesResult, REDACTED};
 pub use masterror::{AppError, AppErrorKind, AppResult};
@@ -1,8 +1,8 @@
 use std::process::ExitCode;
 
 use tracing::{dispatcher, info};
-use zalo_bot::init_tracing;
-use zalo_types::{AppError, ConfigLoader};
+use zalo_bot::AppBootstrap;
+use zalo_types::AppError;
 
 fn main() -> ExitCode {
     match run() {
@@ -15,14 +15,15 @@ fn main() -> ExitCode {
 }
 
 fn run() -> Result<(), AppError> {
-    let config = ConfigLoader::default().load()?;
+    let bootstrap = AppBootstrap::from_env()?;
 
     if !dispatcher::has_been_set() {
-        init_tracing(&config)?;
+        bootstrap.install_tracing()?;
     }
 
     info!(
-        environment = config.environment().as_str(),
+        environment = bootstrap.config().environment().as_str(),
+        webhook_verification = bootstrap.verifier().is_some(),
         "bot demo ready"
     );
 